@@ -0,0 +1,141 @@
+use std::cell::RefCell;
+use std::process;
+use std::rc::Rc;
+
+use mm::app::{App, IdleManager, Runtime, Screenshots, Splash};
+use mm::controller::{Controller, Error};
+use mm::devices::{get_device, HidApi};
+use mm::events::{
+    Button, Event, EventBus, EventFilter, EventHandler, EventKind, Propagation, Scheduler,
+};
+use mm::gui::display::{Canvas, Pixel};
+use mm::gui::font::{NX5, NX6};
+use mm::gui::ui::{ListPanel, Surface, TabPanel, TextPanel};
+
+mod rainbow;
+
+use rainbow::Rainbow;
+
+fn setup_ui(height: usize, width: usize) -> Surface<TabPanel> {
+    let mut surface: Surface<TabPanel> = Surface::new(height, width);
+
+    let mut text_panel1 = TextPanel::new();
+    text_panel1.set_text("This\nis some\ntext\n\nHello\nWorld\n\nThis is a new\nworld\nof\nstuff");
+
+    let mut text_panel2 = TextPanel::new();
+    text_panel2.set_markup(
+        "The *quick* brown fox jumps over the _lazy_ dog.\n\n~Long lines~ are wrapped to fit the display.",
+    );
+
+    let mut list_panel: ListPanel<u8> = ListPanel::new();
+    list_panel.add_item(0, "Item A");
+    list_panel.add_item(1, "Item B");
+    list_panel.add_item(2, "Item C");
+    list_panel.add_item(3, "Item D");
+    list_panel.add_item(4, "Item E");
+    list_panel.add_item(5, "Item F");
+    list_panel.add_item(6, "Item G");
+    list_panel.add_item(7, "Item H");
+    list_panel.add_item(8, "Item I");
+    list_panel.add_item(9, "Item J");
+
+    let mut panel = TabPanel::new();
+    panel.set_font(&NX6);
+    panel.add_tab(0, "FIRST", Box::new(text_panel1));
+    panel.add_tab(1, "SECOND", Box::new(text_panel2));
+    panel.add_tab(2, "THIRD", Box::new(list_panel));
+    surface.set_child(panel);
+
+    surface
+}
+
+///
+/// Demo application, a tab panel UI with a rainbow pad animation
+///
+struct Demo {
+    surface: Rc<RefCell<Surface<TabPanel>>>,
+    rainbow: Rc<RefCell<Rainbow>>,
+    bus: EventBus,
+    show_metrics: bool,
+}
+
+impl Demo {
+    fn new(scheduler: &mut Scheduler, height: usize, width: usize) -> Self {
+        let surface = Rc::new(RefCell::new(setup_ui(height, width)));
+        let rainbow = Rc::new(RefCell::new(Rainbow::new(scheduler)));
+
+        let mut bus = EventBus::new();
+        bus.subscribe(surface.clone(), EventFilter::all(), 0, Propagation::Consume);
+        bus.subscribe(
+            rainbow.clone(),
+            EventFilter::all()
+                .kind(EventKind::Button)
+                .kind(EventKind::Timer)
+                .buttons(&[Button::Play]),
+            10,
+            Propagation::Consume,
+        );
+
+        Demo {
+            surface,
+            rainbow,
+            bus,
+            show_metrics: false,
+        }
+    }
+}
+
+impl EventHandler for Demo {
+    fn handle(&mut self, event: &Event) -> bool {
+        // View toggles the metrics debug page
+        if let Event::ButtonChange(Button::View, true, _) = event {
+            self.show_metrics = !self.show_metrics;
+            self.surface.borrow_mut().set_repaint();
+            return true;
+        }
+        self.bus.dispatch(event)
+    }
+}
+
+impl App for Demo {
+    fn render(&mut self, ctlr: &mut dyn Controller) {
+        if self.show_metrics {
+            let text = match ctlr.metrics() {
+                Some(metrics) => format!("METRICS\n\n{}", metrics),
+                None => String::from("No metrics"),
+            };
+            let display = ctlr.display();
+            display.fill(Pixel::Off);
+            for (row, line) in text.lines().enumerate() {
+                let font = if row == 0 { &NX6 } else { &NX5 };
+                display.print(line, row, 0, font, Pixel::On);
+            }
+        } else {
+            self.surface.borrow_mut().paint(ctlr.display());
+        }
+        self.rainbow.borrow_mut().render(ctlr);
+    }
+}
+
+fn run() -> Result<(), Error> {
+    let hid_api = HidApi::new()?;
+    let ctlr = get_device(&hid_api)?;
+    let (height, width) = (ctlr.display.height(), ctlr.display.width());
+
+    let mut runtime = Runtime::new(ctlr);
+    runtime.set_splash(Some(Splash::new()));
+    runtime.set_idle(Some(IdleManager::new()));
+    runtime.set_screenshots(Some(Screenshots::new()));
+    let mut demo = Demo::new(runtime.scheduler(), height, width);
+    runtime.run(&mut demo)
+}
+
+fn main() {
+    // Diagnostics are enabled with RUST_LOG, eg RUST_LOG=mm=debug
+    env_logger::init();
+
+    if let Err(err) = run() {
+        eprintln!("Error: {}", err);
+        process::exit(1);
+    }
+}
@@ -0,0 +1,100 @@
+use mm::colour::Colour;
+use mm::controller::Controller;
+use mm::events::{Button, Event, EventHandler, Scheduler, TimerId};
+use rand::Rng;
+use std::time::Duration;
+
+pub struct Light {
+    x: usize,
+    y: usize,
+    v: usize,
+    colour: Colour,
+}
+
+pub struct Rainbow {
+    active: bool,
+    dirty: bool,
+    timer: TimerId,
+    next_frame: bool,
+    lights: Vec<Light>,
+}
+
+impl Rainbow {
+    pub fn new(scheduler: &mut Scheduler) -> Self {
+        let mut rng = rand::thread_rng();
+
+        let mut lights: Vec<Light> = Vec::new();
+        for _ in 0..8 {
+            lights.push(Light {
+                x: rng.gen_range(0..4),
+                y: rng.gen_range(0..4),
+                v: 1,
+                colour: Colour::BLACK,
+            });
+        }
+
+        Self {
+            active: false,
+            dirty: false,
+            timer: scheduler.repeat(Duration::from_millis(500)),
+            next_frame: false,
+            lights,
+        }
+    }
+
+    pub fn render(&mut self, ctlr: &mut dyn Controller) {
+        if self.dirty {
+            // Overlay the animation on the current LED state and restore it when stopped
+            if self.active {
+                ctlr.push_leds();
+                ctlr.set_button_led(Button::Play, Colour::WHITE);
+            } else {
+                ctlr.pop_leds();
+                ctlr.set_button_led(Button::Play, Colour::BLACK);
+            }
+            self.dirty = false;
+        }
+
+        if self.active && self.next_frame {
+            let mut rng = rand::thread_rng();
+
+            self.next_frame = false;
+
+            // Render lights
+            for pad in 0..16 {
+                ctlr.set_pad_led(pad as u8, Colour::BLACK);
+            }
+            for light in self.lights.iter() {
+                let pad = light.x + (light.y * 4);
+                ctlr.set_pad_led(pad as u8, light.colour);
+            }
+
+            // Move lights
+            for light in self.lights.iter_mut() {
+                light.y += light.v;
+                if light.y >= 4 {
+                    light.y = 0;
+                    light.x = rng.gen_range(0..4);
+                    light.colour = Colour::random();
+                }
+            }
+        }
+    }
+}
+
+impl EventHandler for Rainbow {
+    fn handle(&mut self, event: &Event) -> bool {
+        match event {
+            Event::ButtonChange(Button::Play, true, _) => {
+                self.active = !self.active;
+                self.dirty = true;
+                true
+            }
+            Event::Timer(timer) if *timer == self.timer => {
+                self.next_frame = true;
+                true
+            }
+            _ => false,
+        }
+    }
+}
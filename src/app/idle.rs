@@ -0,0 +1,407 @@
+use crate::colour::Colour;
+use crate::controller::{Controller, Led, LedSnapshot};
+use crate::diagnostics::debug;
+use crate::events::{Button, Event};
+use crate::gui::bitmap::{Bitmap, BlitMode};
+use crate::gui::display::{Canvas, MonochromeCanvas, Pixel};
+use crate::gui::logo::{LOGO, LOGO_HEIGHT, LOGO_WIDTH};
+use std::time::{Duration, Instant};
+
+///
+/// What is shown on the display once the screensaver starts
+///
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Screensaver {
+    /// Switch all pixels off
+    Blank,
+
+    /// Logo bouncing around the display
+    Logo,
+}
+
+/// Control that woke the manager, its remaining events are swallowed
+#[derive(Copy, Clone)]
+enum Wake {
+    Button(Button),
+    Pad(u8),
+}
+
+///
+/// Dims the LEDs and starts a screensaver when no input has been received
+///
+/// The first input after going idle restores the LEDs and display and is not
+/// passed on to the application (including the release of the button or pad
+/// that was pressed). LEDs changed by the application while dimmed are dimmed
+/// too and kept when the LEDs are restored. While the screensaver is showing the
+/// application is not rendered.
+///
+pub struct IdleManager {
+    dim_timeout: Option<Duration>,
+    screensaver_timeout: Option<Duration>,
+    dim_level: u8,
+    screensaver: Screensaver,
+    last_input: Instant,
+    // LEDs before dimming and the dimmed LEDs that were set
+    leds: Option<(LedSnapshot, LedSnapshot)>,
+    display: Option<MonochromeCanvas>,
+    wake: Option<Wake>,
+    logo_bounds: (usize, usize, usize, usize),
+    logo_position: (usize, usize),
+    logo_velocity: (isize, isize),
+}
+
+impl IdleManager {
+    pub const DEFAULT_DIM_TIMEOUT: Duration = Duration::from_secs(5 * 60);
+    pub const DEFAULT_SCREENSAVER_TIMEOUT: Duration = Duration::from_secs(10 * 60);
+    pub const DEFAULT_DIM_LEVEL: u8 = 32;
+
+    pub fn new() -> Self {
+        let logo_bounds = logo_bounds();
+        IdleManager {
+            dim_timeout: Some(Self::DEFAULT_DIM_TIMEOUT),
+            screensaver_timeout: Some(Self::DEFAULT_SCREENSAVER_TIMEOUT),
+            dim_level: Self::DEFAULT_DIM_LEVEL,
+            screensaver: Screensaver::Logo,
+            last_input: Instant::now(),
+            leds: None,
+            display: None,
+            wake: None,
+            logo_bounds,
+            logo_position: (logo_bounds.0, logo_bounds.1),
+            logo_velocity: (1, 1),
+        }
+    }
+
+    ///
+    /// Set the time without input before the LEDs are dimmed, `None` to disable
+    ///
+    pub fn set_dim_timeout(&mut self, timeout: Option<Duration>) {
+        self.dim_timeout = timeout;
+    }
+
+    ///
+    /// Set the time without input before the screensaver starts, `None` to disable
+    ///
+    pub fn set_screensaver_timeout(&mut self, timeout: Option<Duration>) {
+        self.screensaver_timeout = timeout;
+    }
+
+    ///
+    /// Set the brightness of dimmed LEDs, 0 (off) to 255 (unchanged)
+    ///
+    pub fn set_dim_level(&mut self, level: u8) {
+        self.dim_level = level;
+    }
+
+    ///
+    /// Set what is shown by the screensaver
+    ///
+    pub fn set_screensaver(&mut self, screensaver: Screensaver) {
+        self.screensaver = screensaver;
+    }
+
+    /// LEDs are dimmed or the screensaver is showing
+    pub fn is_idle(&self) -> bool {
+        self.leds.is_some() || self.display.is_some()
+    }
+
+    /// Screensaver is showing, the application should not render
+    pub fn is_screensaver_showing(&self) -> bool {
+        self.display.is_some()
+    }
+
+    ///
+    /// Record an input event, restoring the controller if idle
+    ///
+    /// Returns true if the event was used to wake up and should not be passed
+    /// on to the application.
+    ///
+    pub fn handle_input(&mut self, event: &Event, ctlr: &mut dyn Controller) -> bool {
+        if let Event::Timer(_) = event {
+            return false;
+        }
+        self.last_input = Instant::now();
+
+        // Swallow the rest of the press that woke us up, other controls may
+        // change before it is released
+        match (self.wake, event) {
+            (Some(Wake::Button(woken)), Event::ButtonChange(button, false, _))
+                if woken == *button =>
+            {
+                self.wake = None;
+                return true;
+            }
+            (Some(Wake::Pad(woken)), Event::PadChange(pad, velocity, _)) if woken == *pad => {
+                if *velocity == 0 {
+                    self.wake = None;
+                }
+                return true;
+            }
+            _ => {}
+        }
+
+        if !self.is_idle() {
+            return false;
+        }
+
+        debug!("waking from idle");
+        self.restore(ctlr);
+        self.wake = match event {
+            Event::ButtonChange(button, true, _) => Some(Wake::Button(*button)),
+            Event::PadChange(pad, velocity, _) if *velocity > 0 => Some(Wake::Pad(*pad)),
+            _ => None,
+        };
+        true
+    }
+
+    ///
+    /// Dim the LEDs or update the screensaver, called once per frame
+    ///
+    pub fn update(&mut self, ctlr: &mut dyn Controller) {
+        let idle = self.last_input.elapsed();
+
+        if self.leds.is_none() && self.dim_timeout.is_some_and(|t| idle >= t) {
+            debug!("dimming LEDs");
+            let snapshot = ctlr.led_snapshot();
+            for (led, colour) in snapshot.iter() {
+                self.dim(ctlr, led, colour);
+            }
+            self.leds = Some((snapshot, ctlr.led_snapshot()));
+        } else {
+            self.update_dimmed(ctlr);
+        }
+
+        if self.display.is_none() && self.screensaver_timeout.is_some_and(|t| idle >= t) {
+            debug!("starting screensaver");
+            let display = ctlr.display();
+            self.display = Some(MonochromeCanvas::from_buffer(
+                display.width(),
+                display.height(),
+                display.data(),
+            ));
+            display.fill(Pixel::Off);
+        }
+
+        if self.display.is_some() && self.screensaver == Screensaver::Logo {
+            self.paint_logo(ctlr.display());
+        }
+    }
+
+    /// Set an LED to a dimmed colour
+    fn dim(&self, ctlr: &mut dyn Controller, led: Led, colour: Colour) {
+        let colour = colour.scale(self.dim_level);
+        match led {
+            Led::Button(button) => ctlr.set_button_led(button, colour),
+            Led::Pad(pad) => ctlr.set_pad_led(pad, colour),
+        }
+    }
+
+    ///
+    /// Dim any LEDs the application has changed since they were dimmed, the new
+    /// colours replace those in the snapshot so they are kept when restored
+    ///
+    fn update_dimmed(&mut self, ctlr: &mut dyn Controller) {
+        if let Some((mut snapshot, dimmed)) = self.leds.take() {
+            let changes = dimmed.diff(&ctlr.led_snapshot());
+            for (led, colour) in changes.iter() {
+                snapshot.set(*led, *colour);
+                self.dim(ctlr, *led, *colour);
+            }
+            let dimmed = if changes.is_empty() {
+                dimmed
+            } else {
+                ctlr.led_snapshot()
+            };
+            self.leds = Some((snapshot, dimmed));
+        }
+    }
+
+    /// Restore the LEDs and display to their state before going idle
+    fn restore(&mut self, ctlr: &mut dyn Controller) {
+        self.update_dimmed(ctlr);
+        if let Some((snapshot, _)) = self.leds.take() {
+            ctlr.restore_leds(&snapshot);
+        }
+        if let Some(display) = self.display.take() {
+            ctlr.display().copy_from(&display);
+        }
+    }
+
+    /// Move the logo one step and paint it
+    fn paint_logo(&mut self, canvas: &mut MonochromeCanvas) {
+        let (left, top, width, height) = self.logo_bounds;
+        let max_x = canvas.width().saturating_sub(width);
+        let max_y = canvas.height().saturating_sub(height);
+
+        let (x, dx) = bounce(self.logo_position.0, self.logo_velocity.0, max_x);
+        let (y, dy) = bounce(self.logo_position.1, self.logo_velocity.1, max_y);
+        self.logo_position = (x, y);
+        self.logo_velocity = (dx, dy);
+
+        // Position the whole logo so its lit pixels start at (x, y)
+        canvas.fill(Pixel::Off);
+        canvas.blit(
+            &logo(),
+            x as i32 - left as i32,
+            y as i32 - top as i32,
+            BlitMode::Or,
+        );
+    }
+}
+
+impl Default for IdleManager {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Move a position by a velocity, reversing direction at either end of the range
+fn bounce(position: usize, velocity: isize, max: usize) -> (usize, isize) {
+    if max == 0 {
+        return (0, velocity);
+    }
+    let next = position as isize + velocity;
+    if next < 0 || next > max as isize {
+        let velocity = -velocity;
+        (
+            (position as isize + velocity).clamp(0, max as isize) as usize,
+            velocity,
+        )
+    } else {
+        (next as usize, velocity)
+    }
+}
+
+fn logo() -> Bitmap<'static> {
+    Bitmap::new(LOGO_WIDTH, LOGO_HEIGHT, &LOGO as &[u8])
+}
+
+/// Bounding box (left, top, width, height) of the lit pixels of the logo
+fn logo_bounds() -> (usize, usize, usize, usize) {
+    let logo = logo();
+    let (mut left, mut top, mut right, mut bottom) = (LOGO_WIDTH, LOGO_HEIGHT, 0, 0);
+    for y in 0..LOGO_HEIGHT {
+        for x in 0..LOGO_WIDTH {
+            if logo.pixel(x, y) {
+                left = left.min(x);
+                top = top.min(y);
+                right = right.max(x + 1);
+                bottom = bottom.max(y + 1);
+            }
+        }
+    }
+    (
+        left,
+        top,
+        right.saturating_sub(left),
+        bottom.saturating_sub(top),
+    )
+}
+
+#[cfg(all(test, feature = "simulator"))]
+mod tests {
+    use super::*;
+    use crate::devices::Simulator;
+
+    /// Manager that goes idle on the next update
+    fn idle_manager(dim: bool, screensaver: bool) -> IdleManager {
+        let mut idle = IdleManager::new();
+        idle.set_dim_timeout(dim.then_some(Duration::ZERO));
+        idle.set_screensaver_timeout(screensaver.then_some(Duration::ZERO));
+        idle.set_screensaver(Screensaver::Blank);
+        idle
+    }
+
+    fn press(button: Button, pressed: bool) -> Event {
+        Event::ButtonChange(button, pressed, false)
+    }
+
+    #[test]
+    fn dims_and_restores_leds() {
+        let mut sim = Simulator::new();
+        sim.set_button_led(Button::Play, Colour::WHITE);
+        sim.set_pad_led(0, Colour::RED);
+        let mut idle = idle_manager(true, false);
+
+        idle.update(&mut sim);
+        assert!(idle.is_idle());
+        assert!(!idle.is_screensaver_showing());
+        let level = IdleManager::DEFAULT_DIM_LEVEL;
+        assert_eq!(sim.button_led(Button::Play), Colour::WHITE.scale(level));
+        assert_eq!(sim.pad_led(0), Colour::RED.scale(level));
+
+        // The wake press and release are swallowed
+        idle.set_dim_timeout(None);
+        assert!(idle.handle_input(&press(Button::Rec, true), &mut sim));
+        assert!(!idle.is_idle());
+        assert_eq!(sim.button_led(Button::Play), Colour::WHITE);
+        assert_eq!(sim.pad_led(0), Colour::RED);
+        assert!(idle.handle_input(&press(Button::Rec, false), &mut sim));
+        assert!(!idle.handle_input(&press(Button::Rec, true), &mut sim));
+    }
+
+    #[test]
+    fn leds_changed_while_dimmed_are_kept() {
+        let mut sim = Simulator::new();
+        sim.set_pad_led(0, Colour::RED);
+        let mut idle = idle_manager(true, false);
+        idle.update(&mut sim);
+        idle.set_dim_timeout(None);
+
+        // Eg from a timer event or render
+        sim.set_pad_led(0, Colour::GREEN);
+        idle.update(&mut sim);
+        let level = IdleManager::DEFAULT_DIM_LEVEL;
+        assert_eq!(sim.pad_led(0), Colour::GREEN.scale(level));
+
+        // Changed after the last update
+        sim.set_pad_led(1, Colour::BLUE);
+        assert!(idle.handle_input(&Event::PadChange(5, 10, false), &mut sim));
+        assert_eq!(sim.pad_led(0), Colour::GREEN);
+        assert_eq!(sim.pad_led(1), Colour::BLUE);
+    }
+
+    #[test]
+    fn wake_release_is_swallowed_after_other_events() {
+        let mut sim = Simulator::new();
+        let mut idle = idle_manager(true, false);
+        idle.update(&mut sim);
+        idle.set_dim_timeout(None);
+
+        assert!(idle.handle_input(&Event::PadChange(3, 100, false), &mut sim));
+        assert!(!idle.handle_input(&press(Button::Play, true), &mut sim));
+        assert!(idle.handle_input(&Event::PadChange(3, 50, false), &mut sim));
+        assert!(!idle.handle_input(&press(Button::Play, false), &mut sim));
+        assert!(idle.handle_input(&Event::PadChange(3, 0, false), &mut sim));
+        assert!(!idle.handle_input(&Event::PadChange(3, 90, false), &mut sim));
+    }
+
+    #[test]
+    fn screensaver_restores_display() {
+        let mut sim = Simulator::new();
+        sim.display.fill_rect(10, 10, 20, 20, Pixel::On);
+        let before = MonochromeCanvas::from_buffer(128, 64, sim.display.data());
+        let mut idle = idle_manager(false, true);
+
+        idle.update(&mut sim);
+        assert!(idle.is_screensaver_showing());
+        assert_eq!(sim.display, MonochromeCanvas::new(128, 64));
+
+        idle.set_screensaver_timeout(None);
+        assert!(idle.handle_input(&press(Button::Play, true), &mut sim));
+        assert!(!idle.is_idle());
+        assert_eq!(sim.display, before);
+    }
+
+    #[test]
+    fn timers_do_not_wake() {
+        let mut sim = Simulator::new();
+        let mut scheduler = crate::events::Scheduler::new();
+        let timer = scheduler.once(Duration::ZERO);
+        let mut idle = idle_manager(true, false);
+        idle.update(&mut sim);
+
+        assert!(!idle.handle_input(&Event::Timer(timer), &mut sim));
+        assert!(idle.is_idle());
+    }
+}
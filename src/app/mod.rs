@@ -0,0 +1,363 @@
+///
+/// # Application framework
+///
+/// Provides a main loop that polls the controller for input at a fixed rate and
+/// updates/renders an application at a target frame rate.
+///
+mod idle;
+mod screenshot;
+mod splash;
+
+pub use idle::{IdleManager, Screensaver};
+pub use screenshot::Screenshots;
+pub use splash::Splash;
+
+use crate::controller::{Controller, Error};
+use crate::diagnostics::{debug, trace_span, warn};
+use crate::events::{EventContext, EventHandler, EventTask, Scheduler};
+use crate::gui::display::{Canvas, Pixel};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+///
+/// Application hooks called by the `Runtime`
+///
+/// Events from the controller and scheduler are passed to `EventHandler::handle`.
+///
+pub trait App: EventHandler {
+    ///
+    /// Called once before the main loop starts
+    ///
+    fn init(
+        &mut self,
+        _ctlr: &mut dyn Controller,
+        _scheduler: &mut Scheduler,
+    ) -> Result<(), Error> {
+        Ok(())
+    }
+
+    ///
+    /// Update application state, called once per frame before render
+    ///
+    fn update(&mut self, _scheduler: &mut Scheduler) {}
+
+    ///
+    /// Render the application to the display and LEDs, called once per frame
+    ///
+    fn render(&mut self, ctlr: &mut dyn Controller);
+
+    ///
+    /// Called once the main loop has exited, before the display and LEDs are cleared
+    ///
+    fn shutdown(&mut self, _ctlr: &mut dyn Controller) {}
+}
+
+///
+/// Runs an `App` against a controller
+///
+/// The loop exits on Ctrl-C (SIGINT), SIGTERM or when the stop flag is set, the
+/// display is then cleared and all LEDs switched off.
+///
+/// The startup splash, idle dimming/screensaver and screenshots are disabled
+/// unless enabled with `set_splash`, `set_idle` and `set_screenshots`.
+///
+pub struct Runtime<C: Controller> {
+    controller: C,
+    scheduler: Scheduler,
+    idle: Option<IdleManager>,
+    screenshots: Option<Screenshots>,
+    splash: Option<Splash>,
+    frame_rate: u32,
+    poll_rate: u32,
+    stop: Arc<AtomicBool>,
+}
+
+impl<C: Controller> Runtime<C> {
+    pub const DEFAULT_FRAME_RATE: u32 = 30;
+    pub const DEFAULT_POLL_RATE: u32 = 300;
+
+    pub fn new(controller: C) -> Self {
+        Runtime {
+            controller,
+            scheduler: Scheduler::new(),
+            idle: None,
+            screenshots: None,
+            splash: None,
+            frame_rate: Self::DEFAULT_FRAME_RATE,
+            poll_rate: Self::DEFAULT_POLL_RATE,
+            stop: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    ///
+    /// Set the target number of frames (update and render) per second
+    ///
+    pub fn set_frame_rate(&mut self, fps: u32) {
+        self.frame_rate = fps.max(1);
+    }
+
+    ///
+    /// Set the number of times per second the controller is ticked for input
+    ///
+    pub fn set_poll_rate(&mut self, hz: u32) {
+        self.poll_rate = hz.max(1);
+    }
+
+    /// The controller being run
+    pub fn controller(&mut self) -> &mut C {
+        &mut self.controller
+    }
+
+    /// Scheduler used to generate timer events
+    pub fn scheduler(&mut self) -> &mut Scheduler {
+        &mut self.scheduler
+    }
+
+    ///
+    /// Set the splash shown while the application initialises, `None` to skip
+    ///
+    pub fn set_splash(&mut self, splash: Option<Splash>) {
+        self.splash = splash;
+    }
+
+    ///
+    /// Set the idle manager used to dim LEDs and show a screensaver, `None` to
+    /// disable
+    ///
+    pub fn set_idle(&mut self, idle: Option<IdleManager>) {
+        self.idle = idle;
+    }
+
+    /// Idle manager, if enabled
+    pub fn idle(&mut self) -> Option<&mut IdleManager> {
+        self.idle.as_mut()
+    }
+
+    ///
+    /// Set the screenshot key combination and directory, `None` to disable
+    ///
+    pub fn set_screenshots(&mut self, screenshots: Option<Screenshots>) {
+        self.screenshots = screenshots;
+    }
+
+    /// Screenshot key combination and directory, if enabled
+    pub fn screenshots(&mut self) -> Option<&mut Screenshots> {
+        self.screenshots.as_mut()
+    }
+
+    ///
+    /// Flag that stops the main loop when set to true
+    ///
+    pub fn stop_handle(&self) -> Arc<AtomicBool> {
+        self.stop.clone()
+    }
+
+    ///
+    /// Run the application until stopped
+    ///
+    pub fn run(&mut self, app: &mut dyn App) -> Result<(), Error> {
+        signal_hook::flag::register(signal_hook::consts::SIGINT, self.stop.clone())?;
+        signal_hook::flag::register(signal_hook::consts::SIGTERM, self.stop.clone())?;
+
+        debug!(
+            frame_rate = self.frame_rate,
+            poll_rate = self.poll_rate,
+            "starting runtime"
+        );
+        if let Some(splash) = &self.splash {
+            splash.run(&mut self.controller, &self.stop)?;
+        }
+        app.init(&mut self.controller, &mut self.scheduler)?;
+
+        let result = self.main_loop(app);
+
+        app.shutdown(&mut self.controller);
+        self.controller.display().fill(Pixel::Off);
+        self.controller.clear_leds();
+        // Report the error that stopped the loop rather than any from clearing up
+        if let Err(err) = self.controller.flush() {
+            warn!(%err, "unable to clear the controller on shutdown");
+        }
+
+        result
+    }
+
+    fn main_loop(&mut self, app: &mut dyn App) -> Result<(), Error> {
+        let frame_interval = Duration::from_secs(1) / self.frame_rate;
+        let poll_interval = Duration::from_secs(1) / self.poll_rate;
+        let mut next_frame = Instant::now();
+        let mut next_poll = next_frame;
+
+        while !self.stop.load(Ordering::Relaxed) {
+            let now = Instant::now();
+
+            if now >= next_poll {
+                let _span = trace_span!("poll").entered();
+                let mut context = EventContext::new();
+                self.controller.tick(&mut context)?;
+                self.scheduler.tick(&mut context)?;
+                if let Some(metrics) = self.controller.metrics() {
+                    metrics.events_dispatching();
+                }
+                while let Some(event) = context.events.pop_front() {
+                    let ctlr = &mut self.controller;
+                    let consumed = self
+                        .idle
+                        .as_mut()
+                        .is_some_and(|idle| idle.handle_input(&event, ctlr))
+                        || self
+                            .screenshots
+                            .as_mut()
+                            .is_some_and(|screenshots| screenshots.handle_input(&event, ctlr));
+                    if !consumed {
+                        app.handle(&event);
+                    }
+                }
+                if let Some(metrics) = self.controller.metrics() {
+                    metrics.events_dispatched(Instant::now());
+                }
+                next_poll = advance(next_poll, poll_interval, now);
+            }
+
+            if now >= next_frame {
+                let _span = trace_span!("frame").entered();
+                app.update(&mut self.scheduler);
+                let screensaver = match self.idle.as_mut() {
+                    Some(idle) => {
+                        idle.update(&mut self.controller);
+                        idle.is_screensaver_showing()
+                    }
+                    None => false,
+                };
+                if !screensaver {
+                    app.render(&mut self.controller);
+                }
+                next_frame = advance(next_frame, frame_interval, now);
+            }
+
+            let wake = next_poll.min(next_frame);
+            let now = Instant::now();
+            if wake > now {
+                thread::sleep(wake - now);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Advance a deadline by an interval, skipping missed intervals if running behind
+fn advance(deadline: Instant, interval: Duration, now: Instant) -> Instant {
+    let next = deadline + interval;
+    if next <= now {
+        now + interval
+    } else {
+        next
+    }
+}
+
+#[cfg(all(test, feature = "simulator"))]
+mod tests {
+    use super::*;
+    use crate::colour::Colour;
+    use crate::devices::Simulator;
+    use crate::events::{Button, Event};
+    use crate::gui::display::MonochromeCanvas;
+    use crate::gui::font::NX5;
+
+    /// Lights the Play LED and shows the state on the display while Play is held
+    #[derive(Default)]
+    struct Player {
+        playing: bool,
+        frames: usize,
+        stop: Option<Arc<AtomicBool>>,
+    }
+
+    impl EventHandler for Player {
+        fn handle(&mut self, event: &Event) -> bool {
+            match event {
+                Event::ButtonChange(Button::Play, pressed, _) => {
+                    self.playing = *pressed;
+                    true
+                }
+                _ => false,
+            }
+        }
+    }
+
+    impl App for Player {
+        fn render(&mut self, ctlr: &mut dyn Controller) {
+            let colour = if self.playing {
+                Colour::GREEN
+            } else {
+                Colour::BLACK
+            };
+            ctlr.set_button_led(Button::Play, colour);
+
+            let display = ctlr.display();
+            display.fill(Pixel::Off);
+            if self.playing {
+                display.print("PLAYING", 0, 0, &NX5, Pixel::On);
+            }
+
+            self.frames += 1;
+            if let Some(stop) = &self.stop {
+                stop.store(true, Ordering::Relaxed);
+            }
+        }
+    }
+
+    /// Pass the events generated by the simulator to the app
+    fn dispatch(sim: &mut Simulator, app: &mut dyn App) {
+        let mut context = EventContext::new();
+        sim.tick(&mut context).unwrap();
+        while let Some(event) = context.events.pop_front() {
+            app.handle(&event);
+        }
+    }
+
+    #[test]
+    fn render_input_to_the_simulator() {
+        let mut sim = Simulator::new();
+        let mut app = Player::default();
+
+        sim.inject(Event::ButtonChange(Button::Play, true, false));
+        dispatch(&mut sim, &mut app);
+        app.render(&mut sim);
+
+        let mut expected = MonochromeCanvas::new(128, 64);
+        expected.print("PLAYING", 0, 0, &NX5, Pixel::On);
+        assert_eq!(sim.display, expected);
+        assert_eq!(sim.button_led(Button::Play), Colour::GREEN);
+
+        sim.inject(Event::ButtonChange(Button::Play, false, false));
+        dispatch(&mut sim, &mut app);
+        app.render(&mut sim);
+
+        assert_eq!(sim.display, MonochromeCanvas::new(128, 64));
+        assert_eq!(sim.button_led(Button::Play), Colour::BLACK);
+        assert_eq!(sim.frames(), 2);
+    }
+
+    #[test]
+    fn runtime_clears_the_controller_on_exit() {
+        let mut runtime = Runtime::new(Simulator::new());
+        runtime
+            .controller()
+            .inject(Event::ButtonChange(Button::Play, true, false));
+        let mut app = Player {
+            stop: Some(runtime.stop_handle()),
+            ..Player::default()
+        };
+
+        runtime.run(&mut app).unwrap();
+
+        assert!(app.playing);
+        assert_eq!(app.frames, 1);
+        let sim = runtime.controller();
+        assert_eq!(sim.display, MonochromeCanvas::new(128, 64));
+        assert_eq!(sim.button_led(Button::Play), Colour::BLACK);
+    }
+}
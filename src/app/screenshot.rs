@@ -0,0 +1,173 @@
+use crate::controller::{Controller, Error};
+use crate::diagnostics::{info, warn};
+use crate::events::{Button, Event};
+use std::format;
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Format screenshots are saved in
+#[cfg(feature = "png")]
+const EXTENSION: &str = "png";
+#[cfg(not(feature = "png"))]
+const EXTENSION: &str = "pbm";
+
+///
+/// Saves the display to an image file when shift and the screenshot button are
+/// pressed together
+///
+/// Screenshots are named `screenshot-<secs>-<millis>.png` from the time they
+/// were taken (`.pbm` without the `png` feature) and saved to the current
+/// directory unless another is set. The
+/// press and release of the button are not passed on to the application.
+///
+pub struct Screenshots {
+    button: Option<Button>,
+    directory: PathBuf,
+    pressed: bool,
+}
+
+impl Screenshots {
+    pub const DEFAULT_BUTTON: Button = Button::Sampling;
+
+    pub fn new() -> Self {
+        Screenshots {
+            button: Some(Self::DEFAULT_BUTTON),
+            directory: PathBuf::from("."),
+            pressed: false,
+        }
+    }
+
+    ///
+    /// Set the button that takes a screenshot while shift is held, `None` to
+    /// disable
+    ///
+    pub fn set_button(&mut self, button: Option<Button>) {
+        self.button = button;
+    }
+
+    ///
+    /// Set the directory screenshots are saved to
+    ///
+    pub fn set_directory<P: Into<PathBuf>>(&mut self, directory: P) {
+        self.directory = directory.into();
+    }
+
+    ///
+    /// Take a screenshot if the event completes the key combination
+    ///
+    /// Returns true if the event was part of the key combination and should not
+    /// be passed on to the application.
+    ///
+    pub fn handle_input(&mut self, event: &Event, ctlr: &mut dyn Controller) -> bool {
+        match event {
+            Event::ButtonChange(button, true, true) if Some(*button) == self.button => {
+                self.pressed = true;
+                if let Err(err) = self.save(ctlr) {
+                    warn!(%err, "unable to save screenshot");
+                }
+                true
+            }
+            Event::ButtonChange(button, false, _)
+                if self.pressed && Some(*button) == self.button =>
+            {
+                self.pressed = false;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    ///
+    /// Save the current contents of the display, returns the path of the file
+    ///
+    pub fn save(&self, ctlr: &mut dyn Controller) -> Result<PathBuf, Error> {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default();
+        let path = self.directory.join(format!(
+            "screenshot-{}-{:03}.{}",
+            timestamp.as_secs(),
+            timestamp.subsec_millis(),
+            EXTENSION
+        ));
+        ctlr.display().save(&path)?;
+        info!(path = %path.display(), "saved screenshot");
+        Ok(path)
+    }
+}
+
+impl Default for Screenshots {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(all(test, feature = "simulator"))]
+mod tests {
+    use super::*;
+    use crate::devices::Simulator;
+    use crate::events::{EventContext, EventTask};
+    use crate::gui::display::{Canvas, Pixel};
+    use crate::gui::font::NX5;
+    use crate::gui::graphics::Graphics;
+    use crate::gui::image::{Dither, Image};
+    use std::fs;
+    use std::vec::Vec;
+
+    /// Events generated by the simulator that the screenshots did not consume
+    fn handle(sim: &mut Simulator, screenshots: &mut Screenshots, event: Event) -> Vec<Event> {
+        sim.inject(event);
+        let mut context = EventContext::new();
+        sim.tick(&mut context).unwrap();
+        context
+            .events
+            .into_iter()
+            .filter(|event| !screenshots.handle_input(event, sim))
+            .collect()
+    }
+
+    fn files(directory: &PathBuf) -> Vec<PathBuf> {
+        fs::read_dir(directory)
+            .unwrap()
+            .map(|entry| entry.unwrap().path())
+            .collect()
+    }
+
+    #[test]
+    fn shift_sampling_saves_the_display() {
+        let directory = std::env::temp_dir().join(format!("mm-screenshot-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&directory);
+        fs::create_dir_all(&directory).unwrap();
+
+        let mut sim = Simulator::new();
+        sim.display.print("SCREENSHOT", 1, 4, &NX5, Pixel::On);
+        sim.display.draw_circle(100, 32, 20, Pixel::On);
+        let mut screenshots = Screenshots::new();
+        screenshots.set_directory(&directory);
+
+        // Without shift the button is passed on
+        let press = Event::ButtonChange(Button::Sampling, true, false);
+        assert_eq!(handle(&mut sim, &mut screenshots, press).len(), 1);
+        let release = Event::ButtonChange(Button::Sampling, false, false);
+        assert_eq!(handle(&mut sim, &mut screenshots, release).len(), 1);
+        assert!(files(&directory).is_empty());
+
+        let press = Event::ButtonChange(Button::Sampling, true, true);
+        assert!(handle(&mut sim, &mut screenshots, press).is_empty());
+        let release = Event::ButtonChange(Button::Sampling, false, true);
+        assert!(handle(&mut sim, &mut screenshots, release).is_empty());
+
+        let files = files(&directory);
+        assert_eq!(files.len(), 1);
+        let name = files[0].file_name().unwrap().to_string_lossy();
+        assert!(name.starts_with("screenshot-"));
+        assert!(name.ends_with(&format!(".{}", EXTENSION)));
+
+        let image = Image::decode(&fs::read(&files[0]).unwrap()).unwrap();
+        let canvas = image.to_canvas(Dither::default());
+        assert_eq!(canvas.width(), 128);
+        assert_eq!(canvas, sim.display);
+
+        fs::remove_dir_all(&directory).unwrap();
+    }
+}
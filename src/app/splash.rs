@@ -0,0 +1,195 @@
+use crate::colour::Colour;
+use crate::controller::{Controller, Error};
+use crate::gui::bitmap::{Bitmap, BlitMode};
+use crate::gui::display::{Canvas, Pixel};
+use crate::gui::font::NX5;
+use crate::gui::graphics::Graphics;
+use crate::gui::logo::{LOGO, LOGO_HEIGHT, LOGO_WIDTH};
+use std::format;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Pads are laid out in a 4x4 grid
+const PAD_COLUMNS: u8 = 4;
+
+///
+/// Startup splash screen
+///
+/// Shows the logo with a line of device information while sweeping across the
+/// pad LEDs. The splash is run before the application is initialised, the
+/// display is cleared and the LEDs restored once it has finished so the
+/// application starts from a blank display.
+///
+#[derive(Clone, Debug)]
+pub struct Splash {
+    duration: Duration,
+    show_info: bool,
+    led_sweep: bool,
+    colour: Colour,
+}
+
+impl Splash {
+    pub const DEFAULT_DURATION: Duration = Duration::from_millis(1500);
+
+    pub fn new() -> Self {
+        Splash {
+            duration: Self::DEFAULT_DURATION,
+            show_info: true,
+            led_sweep: true,
+            colour: Colour::WHITE,
+        }
+    }
+
+    ///
+    /// Set the time the splash is shown for
+    ///
+    pub fn set_duration(&mut self, duration: Duration) {
+        self.duration = duration;
+    }
+
+    ///
+    /// Show the product, serial number and version of the device
+    ///
+    pub fn set_show_info(&mut self, show_info: bool) {
+        self.show_info = show_info;
+    }
+
+    ///
+    /// Sweep across the pad LEDs
+    ///
+    pub fn set_led_sweep(&mut self, led_sweep: bool) {
+        self.led_sweep = led_sweep;
+    }
+
+    ///
+    /// Set the colour of the LED sweep
+    ///
+    pub fn set_colour(&mut self, colour: Colour) {
+        self.colour = colour;
+    }
+
+    ///
+    /// Paint the splash to the display
+    ///
+    pub fn show(&self, ctlr: &mut dyn Controller) -> Result<(), Error> {
+        let info = ctlr.device_info();
+        let display = ctlr.display();
+
+        // Centre the logo on the display
+        let logo = Bitmap::new(LOGO_WIDTH, LOGO_HEIGHT, &LOGO as &[u8]);
+        display.fill(Pixel::Off);
+        display.blit(
+            &logo,
+            (display.width() as i32 - LOGO_WIDTH as i32) / 2,
+            (display.height() as i32 - LOGO_HEIGHT as i32) / 2,
+            BlitMode::Copy,
+        );
+        if self.show_info {
+            // Opaque so the text is readable over the logo
+            let y = display.height() as i32 - 13;
+            display.draw_text(1, y, &info.product, &NX5, Pixel::On, Some(Pixel::Off));
+            display.draw_text(
+                1,
+                y + 7,
+                &format!("{} v{}", info.serial, info.version),
+                &NX5,
+                Pixel::On,
+                Some(Pixel::Off),
+            );
+        }
+
+        ctlr.flush()
+    }
+
+    ///
+    /// Show the splash and run the LED sweep for the duration of the splash, then
+    /// clear the display
+    ///
+    /// # Arguments
+    ///
+    /// * `ctlr` - Controller to show the splash on
+    /// * `stop` - Finish early if this flag is set
+    ///
+    pub fn run(&self, ctlr: &mut dyn Controller, stop: &AtomicBool) -> Result<(), Error> {
+        let started = Instant::now();
+        self.show(ctlr)?;
+
+        if self.led_sweep {
+            ctlr.push_leds();
+
+            // Light each column of pads in turn, then switch them off again
+            let steps = PAD_COLUMNS as u32 * 2;
+            for step in 0..steps {
+                if stop.load(Ordering::Relaxed) {
+                    break;
+                }
+                let lit = step < PAD_COLUMNS as u32;
+                let column = (step % PAD_COLUMNS as u32) as u8;
+                for row in 0..PAD_COLUMNS {
+                    let colour = if lit { self.colour } else { Colour::BLACK };
+                    ctlr.set_pad_led(column + row * PAD_COLUMNS, colour);
+                }
+                ctlr.flush()?;
+                sleep_until(started + self.duration * (step + 1) / steps, stop);
+            }
+
+            ctlr.pop_leds();
+        } else {
+            sleep_until(started + self.duration, stop);
+        }
+
+        ctlr.display().fill(Pixel::Off);
+        ctlr.flush()
+    }
+}
+
+impl Default for Splash {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Sleep until a deadline, waking early if the stop flag is set
+fn sleep_until(deadline: Instant, stop: &AtomicBool) {
+    const INTERVAL: Duration = Duration::from_millis(20);
+
+    while !stop.load(Ordering::Relaxed) {
+        let now = Instant::now();
+        if now >= deadline {
+            break;
+        }
+        thread::sleep((deadline - now).min(INTERVAL));
+    }
+}
+
+#[cfg(all(test, feature = "simulator"))]
+mod tests {
+    use super::*;
+    use crate::devices::Simulator;
+    use crate::gui::display::MonochromeCanvas;
+
+    #[test]
+    fn run_clears_display_and_restores_leds() {
+        let mut sim = Simulator::new();
+        sim.set_pad_led(0, Colour::RED);
+        let mut splash = Splash::new();
+        splash.set_duration(Duration::from_millis(8));
+
+        splash.run(&mut sim, &AtomicBool::new(false)).unwrap();
+
+        assert_eq!(sim.display, MonochromeCanvas::new(128, 64));
+        assert_eq!(sim.pad_led(0), Colour::RED);
+        assert_eq!(sim.pad_led(1), Colour::BLACK);
+        // The splash followed by the cleared display
+        assert_eq!(sim.frames(), 2);
+    }
+
+    #[test]
+    fn show_fits_the_display() {
+        let mut sim = Simulator::new();
+        sim.display = MonochromeCanvas::new(64, 32);
+        Splash::new().show(&mut sim).unwrap();
+        assert!(sim.display.data().iter().any(|byte| *byte != 0));
+    }
+}
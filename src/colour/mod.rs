@@ -0,0 +1,270 @@
+use crate::error::Error;
+use alloc::string::String;
+use core::str::FromStr;
+#[cfg(feature = "rand")]
+use rand::Rng;
+
+mod palette;
+
+pub use palette::Palette;
+
+///
+/// Colour definition
+///
+/// Can represent RGB or Mono colours
+///
+#[derive(Copy, Clone, Default, Debug, PartialEq, Eq)]
+pub struct Colour {
+    r: u8,
+    g: u8,
+    b: u8,
+}
+
+impl Colour {
+    pub const BLACK: Colour = Colour { r: 0, g: 0, b: 0 };
+    pub const WHITE: Colour = Colour {
+        r: 255,
+        g: 255,
+        b: 255,
+    };
+    pub const RED: Colour = Colour { r: 255, g: 0, b: 0 };
+    pub const GREEN: Colour = Colour { r: 0, g: 255, b: 0 };
+    pub const BLUE: Colour = Colour { r: 0, g: 0, b: 255 };
+
+    /// Construct a new colour
+    pub const fn new(r: u8, g: u8, b: u8) -> Self {
+        Self { r, g, b }
+    }
+
+    #[cfg(feature = "rand")]
+    pub fn random() -> Self {
+        let mut rng = rand::thread_rng();
+        Self {
+            r: rng.gen::<u8>(),
+            g: rng.gen::<u8>(),
+            b: rng.gen::<u8>(),
+        }
+    }
+
+    ///
+    /// Lookup a named colour (case insensitive, spaces, dashes and underscores are ignored)
+    ///
+    pub fn named(name: &str) -> Option<Self> {
+        let name = normalise_name(name);
+        NAMED_COLOURS
+            .iter()
+            .find(|(n, _)| *n == name)
+            .map(|(_, colour)| *colour)
+    }
+
+    /// "Monochrome" representation of the colour
+    pub fn as_1bit(&self) -> u8 {
+        if (self.r > 0x7F) | (self.g > 0x7F) | (self.b > 0x7F) {
+            0xFF
+        } else {
+            0x00
+        }
+    }
+
+    ///
+    /// Brightness of the colour on a mono LED, the level of the brightest component
+    ///
+    pub fn brightness(&self) -> u8 {
+        self.r.max(self.g).max(self.b)
+    }
+
+    /// Return the components of this colour
+    pub fn components(&self) -> (u8, u8, u8) {
+        (self.r, self.g, self.b)
+    }
+
+    ///
+    /// Scale the brightness of the colour, a level of 255 leaves it unchanged
+    ///
+    pub fn scale(&self, level: u8) -> Self {
+        let scale = |c: u8| ((c as u16 * level as u16) / 255) as u8;
+        Self::new(scale(self.r), scale(self.g), scale(self.b))
+    }
+}
+
+///
+/// Parse a colour from one of:
+///
+/// * `#rrggbb` or `#rgb` hex notation
+/// * `rgb(r, g, b)` with decimal components in the range 0-255
+/// * A named colour eg `red` or `light orange`
+///
+impl FromStr for Colour {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let value = s.trim();
+        let invalid = || Error::InvalidColour(String::from(value));
+
+        if let Some(hex) = value.strip_prefix('#') {
+            parse_hex(hex).ok_or_else(invalid)
+        } else if let Some(args) = value.strip_prefix("rgb(").and_then(|v| v.strip_suffix(')')) {
+            parse_rgb(args).ok_or_else(invalid)
+        } else {
+            Colour::named(value).ok_or_else(invalid)
+        }
+    }
+}
+
+///
+/// Formats as `#rrggbb` so a colour can be parsed back
+///
+impl core::fmt::Display for Colour {
+    fn fmt(&self, fmt: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(fmt, "#{:02x}{:02x}{:02x}", self.r, self.g, self.b)
+    }
+}
+
+/// Parse the hex digits of a `#rrggbb` or `#rgb` colour
+fn parse_hex(hex: &str) -> Option<Colour> {
+    if !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+
+    match hex.len() {
+        6 => Some(Colour::new(
+            u8::from_str_radix(&hex[0..2], 16).ok()?,
+            u8::from_str_radix(&hex[2..4], 16).ok()?,
+            u8::from_str_radix(&hex[4..6], 16).ok()?,
+        )),
+        3 => {
+            // Each digit is repeated eg #f80 == #ff8800
+            let digit = |idx: usize| u8::from_str_radix(&hex[idx..=idx], 16).map(|v| v * 0x11);
+            Some(Colour::new(digit(0).ok()?, digit(1).ok()?, digit(2).ok()?))
+        }
+        _ => None,
+    }
+}
+
+/// Parse the arguments of an `rgb(r, g, b)` colour
+fn parse_rgb(args: &str) -> Option<Colour> {
+    let mut components = args.split(',').map(|v| v.trim().parse::<u8>());
+    let colour = Colour::new(
+        components.next()?.ok()?,
+        components.next()?.ok()?,
+        components.next()?.ok()?,
+    );
+    match components.next() {
+        Some(_) => None,
+        None => Some(colour),
+    }
+}
+
+/// Normalise a colour name for comparison
+pub(crate) fn normalise_name(name: &str) -> String {
+    name.chars()
+        .filter(|c| !matches!(c, ' ' | '-' | '_'))
+        .map(|c| c.to_ascii_lowercase())
+        .collect()
+}
+
+///
+/// Colours that can be referenced by name
+///
+/// Names are stored normalised (see `normalise_name`)
+///
+const NAMED_COLOURS: [(&str, Colour); 28] = [
+    ("black", Colour::BLACK),
+    ("off", Colour::BLACK),
+    ("white", Colour::WHITE),
+    ("grey", Colour::new(128, 128, 128)),
+    ("gray", Colour::new(128, 128, 128)),
+    ("red", Colour::RED),
+    ("green", Colour::GREEN),
+    ("blue", Colour::BLUE),
+    ("yellow", Colour::new(255, 255, 0)),
+    ("cyan", Colour::new(0, 255, 255)),
+    ("magenta", Colour::new(255, 0, 255)),
+    ("orange", Colour::new(255, 64, 0)),
+    ("lightorange", Colour::new(255, 128, 0)),
+    ("warmyellow", Colour::new(255, 192, 0)),
+    ("lime", Colour::new(128, 255, 0)),
+    ("mint", Colour::new(0, 255, 128)),
+    ("turquoise", Colour::new(0, 192, 255)),
+    ("plum", Colour::new(64, 0, 255)),
+    ("violet", Colour::new(128, 0, 255)),
+    ("purple", Colour::new(192, 0, 255)),
+    ("fuchsia", Colour::new(255, 0, 128)),
+    ("pink", Colour::new(255, 96, 160)),
+    ("navy", Colour::new(0, 0, 128)),
+    ("teal", Colour::new(0, 128, 128)),
+    ("olive", Colour::new(128, 128, 0)),
+    ("maroon", Colour::new(128, 0, 0)),
+    ("brown", Colour::new(128, 64, 0)),
+    ("amber", Colour::new(255, 160, 0)),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::string::ToString;
+
+    #[test]
+    fn parse_hex() {
+        assert_eq!(
+            "#ff8000".parse::<Colour>().unwrap(),
+            Colour::new(255, 128, 0)
+        );
+        assert_eq!(
+            "#FF8000".parse::<Colour>().unwrap(),
+            Colour::new(255, 128, 0)
+        );
+        assert_eq!("#f80".parse::<Colour>().unwrap(), Colour::new(255, 136, 0));
+        assert!("#ff800".parse::<Colour>().is_err());
+        assert!("#gg8000".parse::<Colour>().is_err());
+        assert!("#+f+f+f".parse::<Colour>().is_err());
+    }
+
+    #[test]
+    fn parse_rgb() {
+        assert_eq!(
+            "rgb(1, 2, 3)".parse::<Colour>().unwrap(),
+            Colour::new(1, 2, 3)
+        );
+        assert_eq!(
+            " rgb(255,0,128) ".parse::<Colour>().unwrap(),
+            Colour::new(255, 0, 128)
+        );
+        assert!("rgb(1, 2)".parse::<Colour>().is_err());
+        assert!("rgb(1, 2, 3, 4)".parse::<Colour>().is_err());
+        assert!("rgb(1, 2, 256)".parse::<Colour>().is_err());
+    }
+
+    #[test]
+    fn parse_named() {
+        assert_eq!("red".parse::<Colour>().unwrap(), Colour::RED);
+        assert_eq!(
+            "Light Orange".parse::<Colour>().unwrap(),
+            Colour::new(255, 128, 0)
+        );
+        assert_eq!(
+            "warm_yellow".parse::<Colour>().unwrap(),
+            Colour::new(255, 192, 0)
+        );
+        match "chartreuse".parse::<Colour>() {
+            Err(Error::InvalidColour(value)) => assert_eq!(value, "chartreuse"),
+            other => panic!("unexpected result {:?}", other),
+        }
+    }
+
+    #[test]
+    fn display_round_trip() {
+        let colour = Colour::new(18, 52, 171);
+        assert_eq!(colour.to_string(), "#1234ab");
+        assert_eq!(colour.to_string().parse::<Colour>().unwrap(), colour);
+    }
+
+    #[test]
+    fn scale_brightness() {
+        assert_eq!(Colour::WHITE.scale(255), Colour::WHITE);
+        assert_eq!(Colour::WHITE.scale(0), Colour::BLACK);
+        assert_eq!(Colour::new(200, 100, 0).scale(128), Colour::new(100, 50, 0));
+        assert_eq!(Colour::WHITE.scale(32).brightness(), 32);
+        assert_eq!(Colour::new(10, 200, 30).brightness(), 200);
+    }
+}
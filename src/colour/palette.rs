@@ -0,0 +1,274 @@
+use super::{normalise_name, Colour};
+use crate::error::Error;
+use alloc::boxed::Box;
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+#[cfg(feature = "std")]
+use std::path::Path;
+
+///
+/// The 16 group colours used by Maschine software
+///
+/// Each colour is the same as the named colour (see `Colour::named`).
+///
+const MASCHINE_COLOURS: [(&str, Colour); 16] = [
+    ("Red", Colour::RED),
+    ("Orange", Colour::new(255, 64, 0)),
+    ("Light Orange", Colour::new(255, 128, 0)),
+    ("Warm Yellow", Colour::new(255, 192, 0)),
+    ("Yellow", Colour::new(255, 255, 0)),
+    ("Lime", Colour::new(128, 255, 0)),
+    ("Green", Colour::GREEN),
+    ("Mint", Colour::new(0, 255, 128)),
+    ("Cyan", Colour::new(0, 255, 255)),
+    ("Turquoise", Colour::new(0, 192, 255)),
+    ("Blue", Colour::BLUE),
+    ("Plum", Colour::new(64, 0, 255)),
+    ("Violet", Colour::new(128, 0, 255)),
+    ("Purple", Colour::new(192, 0, 255)),
+    ("Magenta", Colour::new(255, 0, 255)),
+    ("Fuchsia", Colour::new(255, 0, 128)),
+];
+
+///
+/// Named, ordered collection of colours
+///
+/// Entries can be looked up either by name or by their position, see `lookup`.
+///
+#[derive(Clone, Debug)]
+pub struct Palette {
+    name: String,
+    entries: Vec<(String, Colour)>,
+}
+
+impl Palette {
+    pub fn new(name: &str) -> Self {
+        Palette {
+            name: String::from(name),
+            entries: Vec::new(),
+        }
+    }
+
+    ///
+    /// Maschine style 16 colour group palette
+    ///
+    pub fn maschine() -> Self {
+        let mut palette = Palette::new("maschine");
+        for (name, colour) in MASCHINE_COLOURS.iter() {
+            palette.add(name, *colour);
+        }
+        palette
+    }
+
+    ///
+    /// Parse palettes from a config string
+    ///
+    /// Each palette is started with a `[name]` section header followed by one
+    /// `name = colour` entry per line, colours use any format supported by
+    /// `Colour::from_str`. Blank lines and lines starting with `;` or `#` are
+    /// ignored. Entries before the first section are added to a palette named
+    /// `default`.
+    ///
+    /// ```text
+    /// [drums]
+    /// kick = red
+    /// snare = #ffc000
+    /// hats = rgb(0, 255, 128)
+    /// ```
+    ///
+    pub fn parse(config: &str) -> Result<Vec<Self>, Error> {
+        let mut palettes: Vec<Palette> = Vec::new();
+
+        for (idx, line) in config.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with(';') || line.starts_with('#') {
+                continue;
+            }
+
+            if let Some(name) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+                palettes.push(Palette::new(name.trim()));
+                continue;
+            }
+
+            let (name, value) = match line.split_once('=') {
+                Some((name, value)) if !name.trim().is_empty() => (name.trim(), value),
+                _ => {
+                    return Err(Error::Config {
+                        line: idx + 1,
+                        message: String::from("expected `name = colour`"),
+                        source: None,
+                    })
+                }
+            };
+            let colour = value.parse::<Colour>().map_err(|e| Error::Config {
+                line: idx + 1,
+                message: format!("invalid colour for {:?}", name),
+                source: Some(Box::new(e)),
+            })?;
+
+            if palettes.is_empty() {
+                palettes.push(Palette::new("default"));
+            }
+            if let Some(palette) = palettes.last_mut() {
+                palette.add(name, colour);
+            }
+        }
+
+        Ok(palettes)
+    }
+
+    ///
+    /// Load palettes from a config file (see `Palette::parse` for the format)
+    ///
+    #[cfg(feature = "std")]
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Vec<Self>, Error> {
+        Palette::parse(&std::fs::read_to_string(path)?)
+    }
+
+    /// Name of the palette
+    pub fn name(&self) -> &str {
+        self.name.as_str()
+    }
+
+    /// Append a colour to the palette
+    pub fn add(&mut self, name: &str, colour: Colour) {
+        self.entries.push((String::from(name), colour));
+    }
+
+    /// Number of colours in the palette
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Palette contains no colours
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Get a colour by index (starting from 0)
+    pub fn get(&self, idx: usize) -> Option<Colour> {
+        self.entries.get(idx).map(|(_, colour)| *colour)
+    }
+
+    /// Find a colour by name (compared in the same way as `Colour::named`)
+    pub fn find(&self, name: &str) -> Option<Colour> {
+        let name = normalise_name(name);
+        self.entries
+            .iter()
+            .find(|(n, _)| normalise_name(n) == name)
+            .map(|(_, colour)| *colour)
+    }
+
+    ///
+    /// Lookup a colour by either name or number
+    ///
+    /// Names are matched first (so an entry named `808` is found), otherwise a
+    /// numeric key is the position of the colour starting from 1, matching the
+    /// numbering of Maschine groups.
+    ///
+    pub fn lookup(&self, key: &str) -> Option<Colour> {
+        self.find(key)
+            .or_else(|| match key.trim().parse::<usize>() {
+                Ok(number) if number > 0 => self.get(number - 1),
+                _ => None,
+            })
+    }
+
+    /// Iterate over the name and colour of each entry
+    pub fn iter(&self) -> impl Iterator<Item = (&str, Colour)> {
+        self.entries.iter().map(|(n, c)| (n.as_str(), *c))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONFIG: &str = "
+; Comments and blank lines are ignored
+# either style
+
+[drums]
+kick = red
+snare = #ffc000
+hats = rgb(0, 255, 128)
+808 = blue
+
+[keys]
+Lead Synth = light orange
+";
+
+    #[test]
+    fn parse_sections_and_entries() {
+        let palettes = Palette::parse(CONFIG).unwrap();
+        assert_eq!(palettes.len(), 2);
+
+        let drums = &palettes[0];
+        assert_eq!(drums.name(), "drums");
+        assert_eq!(drums.len(), 4);
+        assert_eq!(drums.get(0), Some(Colour::RED));
+        assert_eq!(drums.get(1), Some(Colour::new(255, 192, 0)));
+        assert_eq!(drums.get(2), Some(Colour::new(0, 255, 128)));
+
+        let keys = &palettes[1];
+        assert_eq!(keys.name(), "keys");
+        assert_eq!(keys.find("lead-synth"), Some(Colour::new(255, 128, 0)));
+    }
+
+    #[test]
+    fn parse_entries_before_a_section_use_default() {
+        let palettes = Palette::parse("kick = red\n[other]\n").unwrap();
+        assert_eq!(palettes[0].name(), "default");
+        assert_eq!(palettes[0].get(0), Some(Colour::RED));
+        assert!(palettes[1].is_empty());
+    }
+
+    #[test]
+    fn parse_reports_line_of_errors() {
+        match Palette::parse("[drums]\nkick = red\nsnare\n") {
+            Err(Error::Config {
+                line: 3,
+                source: None,
+                ..
+            }) => {}
+            other => panic!("unexpected result {:?}", other),
+        }
+        match Palette::parse("[drums]\nkick = #12345\n") {
+            Err(Error::Config {
+                line: 2,
+                source: Some(source),
+                ..
+            }) => assert!(matches!(*source, Error::InvalidColour(_))),
+            other => panic!("unexpected result {:?}", other),
+        }
+    }
+
+    #[test]
+    fn lookup_by_name_before_number() {
+        let drums = Palette::parse(CONFIG).unwrap().remove(0);
+        assert_eq!(drums.lookup("808"), Some(Colour::BLUE));
+        assert_eq!(drums.lookup("Kick"), Some(Colour::RED));
+        assert_eq!(drums.lookup("1"), Some(Colour::RED));
+        assert_eq!(drums.lookup("4"), Some(Colour::BLUE));
+        assert_eq!(drums.lookup("0"), None);
+        assert_eq!(drums.lookup("5"), None);
+        assert_eq!(drums.lookup("tom"), None);
+    }
+
+    #[test]
+    fn maschine_groups_are_numbered_from_one() {
+        let palette = Palette::maschine();
+        assert_eq!(palette.len(), 16);
+        assert_eq!(palette.lookup("1"), Some(Colour::RED));
+        assert_eq!(palette.lookup("16"), palette.lookup("fuchsia"));
+        assert_eq!(palette.lookup("17"), None);
+    }
+
+    #[test]
+    fn maschine_colours_match_named_colours() {
+        for (name, colour) in Palette::maschine().iter() {
+            assert_eq!(Colour::named(name), Some(colour), "{}", name);
+        }
+    }
+}
@@ -1,6 +1,83 @@
 use crate::colour::Colour;
 pub use crate::error::Error;
 use crate::events::{Button, EventTask};
+#[cfg(feature = "gui")]
+use crate::gui::display::MonochromeCanvas;
+#[cfg(feature = "std")]
+use crate::metrics::Metrics;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+///
+/// Identifies an individual LED on a controller
+///
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Led {
+    Button(Button),
+    Pad(u8),
+}
+
+///
+/// Snapshot of the state of all button and pad LEDs
+///
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct LedSnapshot {
+    leds: Vec<(Led, Colour)>,
+}
+
+impl LedSnapshot {
+    pub fn new() -> Self {
+        LedSnapshot { leds: Vec::new() }
+    }
+
+    ///
+    /// Set the colour of an LED in the snapshot
+    ///
+    pub fn set(&mut self, led: Led, colour: Colour) {
+        match self.leds.iter_mut().find(|(l, _)| *l == led) {
+            Some(entry) => entry.1 = colour,
+            None => self.leds.push((led, colour)),
+        }
+    }
+
+    ///
+    /// Colour of an LED in the snapshot
+    ///
+    pub fn get(&self, led: Led) -> Option<Colour> {
+        self.leds
+            .iter()
+            .find(|(l, _)| *l == led)
+            .map(|(_, colour)| *colour)
+    }
+
+    ///
+    /// Iterate over each LED and colour
+    ///
+    pub fn iter(&self) -> impl Iterator<Item = (Led, Colour)> + '_ {
+        self.leds.iter().copied()
+    }
+
+    ///
+    /// Changes required to go from this snapshot to `other`
+    ///
+    pub fn diff(&self, other: &LedSnapshot) -> Vec<(Led, Colour)> {
+        other
+            .iter()
+            .filter(|(led, colour)| self.get(*led) != Some(*colour))
+            .collect()
+    }
+}
+
+///
+/// Identifying information reported by a device
+///
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct DeviceInfo {
+    pub product: String,
+    pub manufacturer: String,
+    pub serial: String,
+    pub version: String,
+}
 
 ///
 /// Common controller behaviours
@@ -9,10 +86,197 @@ pub trait Controller: EventTask {
     ///
     /// Set the State of an Button LED
     ///
+    /// Mono LEDs are lit at the brightness of the colour (see `Colour::brightness`)
+    /// so a dim colour gives a dim LED.
+    ///
     fn set_button_led(&mut self, button: Button, colour: Colour);
 
     ///
     /// Set the colour of a pad
     ///
     fn set_pad_led(&mut self, pad: u8, colour: Colour);
+
+    ///
+    /// Display canvas of the controller
+    ///
+    #[cfg(feature = "gui")]
+    fn display(&mut self) -> &mut MonochromeCanvas;
+
+    ///
+    /// Product, serial number etc of the device
+    ///
+    fn device_info(&self) -> DeviceInfo {
+        DeviceInfo::default()
+    }
+
+    ///
+    /// Send any pending display and LED changes to the device
+    ///
+    fn flush(&mut self) -> Result<(), Error>;
+
+    ///
+    /// Switch off all button and pad LEDs
+    ///
+    fn clear_leds(&mut self) {
+        for (led, _) in self.led_snapshot().iter() {
+            match led {
+                Led::Button(button) => self.set_button_led(button, Colour::BLACK),
+                Led::Pad(pad) => self.set_pad_led(pad, Colour::BLACK),
+            }
+        }
+    }
+
+    ///
+    /// Capture the current state of all button and pad LEDs
+    ///
+    fn led_snapshot(&self) -> LedSnapshot;
+
+    ///
+    /// Restore LEDs from a snapshot
+    ///
+    fn restore_leds(&mut self, snapshot: &LedSnapshot) {
+        for (led, colour) in self.led_snapshot().diff(snapshot) {
+            match led {
+                Led::Button(button) => self.set_button_led(button, colour),
+                Led::Pad(pad) => self.set_pad_led(pad, colour),
+            }
+        }
+    }
+
+    ///
+    /// Save the current LED state onto the stack
+    ///
+    fn push_leds(&mut self);
+
+    ///
+    /// Restore the LED state from the top of the stack
+    ///
+    /// Returns false if the stack was empty
+    ///
+    fn pop_leds(&mut self) -> bool;
+
+    ///
+    /// I/O metrics recorded by the controller, if supported
+    ///
+    #[cfg(feature = "std")]
+    fn metrics(&mut self) -> Option<&mut Metrics> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events::EventContext;
+    use alloc::vec;
+
+    /// Controller that records the LEDs set, using the default `restore_leds`
+    struct Recorder {
+        leds: LedSnapshot,
+        set: Vec<(Led, Colour)>,
+        #[cfg(feature = "gui")]
+        display: MonochromeCanvas,
+    }
+
+    impl Recorder {
+        fn new(leds: LedSnapshot) -> Self {
+            Recorder {
+                leds,
+                set: Vec::new(),
+                #[cfg(feature = "gui")]
+                display: MonochromeCanvas::new(8, 8),
+            }
+        }
+    }
+
+    impl EventTask for Recorder {
+        fn tick(&mut self, _: &mut EventContext) -> Result<(), Error> {
+            Ok(())
+        }
+    }
+
+    impl Controller for Recorder {
+        fn set_button_led(&mut self, button: Button, colour: Colour) {
+            self.leds.set(Led::Button(button), colour);
+            self.set.push((Led::Button(button), colour));
+        }
+
+        fn set_pad_led(&mut self, pad: u8, colour: Colour) {
+            self.leds.set(Led::Pad(pad), colour);
+            self.set.push((Led::Pad(pad), colour));
+        }
+
+        #[cfg(feature = "gui")]
+        fn display(&mut self) -> &mut MonochromeCanvas {
+            &mut self.display
+        }
+
+        fn flush(&mut self) -> Result<(), Error> {
+            Ok(())
+        }
+
+        fn led_snapshot(&self) -> LedSnapshot {
+            self.leds.clone()
+        }
+
+        fn push_leds(&mut self) {}
+
+        fn pop_leds(&mut self) -> bool {
+            false
+        }
+    }
+
+    fn snapshot(leds: &[(Led, Colour)]) -> LedSnapshot {
+        let mut snapshot = LedSnapshot::new();
+        for (led, colour) in leds {
+            snapshot.set(*led, *colour);
+        }
+        snapshot
+    }
+
+    #[test]
+    fn set_replaces_existing_leds() {
+        let mut leds = snapshot(&[(Led::Pad(0), Colour::RED)]);
+        leds.set(Led::Pad(0), Colour::BLUE);
+        assert_eq!(leds.get(Led::Pad(0)), Some(Colour::BLUE));
+        assert_eq!(leds.get(Led::Pad(1)), None);
+        assert_eq!(leds.iter().count(), 1);
+    }
+
+    #[test]
+    fn diff_changed_unchanged_and_missing() {
+        let play = Led::Button(Button::Play);
+        let before = snapshot(&[(play, Colour::WHITE), (Led::Pad(0), Colour::RED)]);
+        let after = snapshot(&[
+            (play, Colour::WHITE),
+            (Led::Pad(0), Colour::BLUE),
+            (Led::Pad(1), Colour::GREEN),
+        ]);
+
+        assert_eq!(
+            before.diff(&after),
+            vec![(Led::Pad(0), Colour::BLUE), (Led::Pad(1), Colour::GREEN)]
+        );
+        assert!(after.diff(&after).is_empty());
+        // LEDs missing from the target are left alone
+        assert!(after.diff(&before).contains(&(Led::Pad(0), Colour::RED)));
+        assert_eq!(after.diff(&before).len(), 1);
+    }
+
+    #[test]
+    fn default_restore_only_sets_changed_leds() {
+        let play = Led::Button(Button::Play);
+        let mut ctlr = Recorder::new(snapshot(&[
+            (play, Colour::WHITE),
+            (Led::Pad(0), Colour::RED),
+        ]));
+        let target = snapshot(&[(play, Colour::WHITE), (Led::Pad(0), Colour::BLACK)]);
+
+        ctlr.restore_leds(&target);
+        assert_eq!(ctlr.set, vec![(Led::Pad(0), Colour::BLACK)]);
+        assert_eq!(ctlr.led_snapshot(), target);
+
+        ctlr.clear_leds();
+        assert_eq!(ctlr.led_snapshot().get(play), Some(Colour::BLACK));
+    }
 }
@@ -1,9 +1,15 @@
-use hidapi::HidDevice;
+use hidapi::{HidDevice, HidError};
 
+use super::LED_BUTTONS;
 use crate::colour::Colour;
-use crate::controller::{Controller, Error};
+use crate::controller::{Controller, DeviceInfo, Error, Led, LedSnapshot};
+use crate::diagnostics::{debug, trace_span, warn};
 use crate::events::{Button, Direction, Event, EventContext, EventTask};
-use crate::gui::display::{MonochromeCanvas, Canvas};
+use crate::gui::display::{Canvas, MonochromeCanvas};
+use crate::metrics::Metrics;
+use alloc::vec;
+use alloc::vec::Vec;
+use std::time::Instant;
 
 const INPUT_BUFFER_SIZE: usize = 512;
 
@@ -93,6 +99,24 @@ const PAD_COUNT: usize = 16;
 const DISPLAY_ADDR: u8 = 0xE0;
 const LED_ADDR: u8 = 0x80;
 
+///
+/// Counters of reports received from the device
+///
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct ReportStats {
+    /// Reports read from the device
+    pub received: u64,
+
+    /// Pad reports skipped to keep up with the device
+    pub dropped: u64,
+
+    /// Reports that were too short or of an unknown type
+    pub invalid: u64,
+
+    /// Reports written to the device
+    pub written: u64,
+}
+
 ///
 /// Maschine Mikro Mk2 Controller
 ///
@@ -104,11 +128,15 @@ pub struct MaschineMikroMk2 {
     pub display: MonochromeCanvas,
     leds: [u8; LED_COUNT],
     leds_dirty: bool,
+    led_stack: Vec<LedSnapshot>,
     button_states: [bool; BUTTON_COUNT],
     shift_pressed: bool,
     pads_data: [u16; PAD_COUNT],
     pads_status: [bool; PAD_COUNT],
     encoder_value: u8,
+    stats: ReportStats,
+    metrics: Metrics,
+    device_info: DeviceInfo,
 }
 
 impl MaschineMikroMk2 {
@@ -122,47 +150,117 @@ impl MaschineMikroMk2 {
             display: MonochromeCanvas::new(128, 64),
             leds: [0; LED_COUNT],
             leds_dirty: true,
+            led_stack: Vec::new(),
             button_states: [false; BUTTON_COUNT],
             shift_pressed: false,
             pads_data: [0; PAD_COUNT],
             pads_status: [false; PAD_COUNT],
             encoder_value: 0,
+            stats: ReportStats::default(),
+            metrics: Metrics::new(),
+            device_info: DeviceInfo::default(),
         }
     }
 
-    /// Send a display frame for the graphics panel
+    ///
+    /// Set the information reported by the device (see `get_device`)
+    ///
+    pub fn set_device_info(&mut self, info: DeviceInfo) {
+        self.device_info = info;
+    }
+
+    /// Counters of reports sent to and received from the device
+    pub fn stats(&self) -> ReportStats {
+        self.stats
+    }
+
+    /// Send the changed regions of the display for the graphics panel
     fn send_frame(&mut self) -> Result<(), Error> {
         if self.display.is_dirty() {
-            for row in (0..8).step_by(2) {
-                // The number of referenced bytes must be <= 256
-                // Eg Column width * number of rows
+            let width = self.display.width();
+
+            // Row bands are sent in pairs as the number of referenced bytes must
+            // be <= 256 (column width * number of rows), only the columns that
+            // have changed in either band are sent.
+            for row in (0..self.display.height() / 8).step_by(2) {
+                let bands = (
+                    self.display.dirty_band(row),
+                    self.display.dirty_band(row + 1),
+                );
+                let (first_row, rows, start, end) = match bands {
+                    (Some((s1, e1)), Some((s2, e2))) => (row, 2, s1.min(s2), e1.max(e2)),
+                    (Some((s, e)), None) => (row, 1, s, e),
+                    (None, Some((s, e))) => (row + 1, 1, s, e),
+                    (None, None) => continue,
+                };
+
                 let mut buffer: Vec<u8> = vec![
                     DISPLAY_ADDR,
-                    0x00,      // Column offset
-                    0x00,      // ?
-                    row as u8, // Row (a row is 8 pixels high)
-                    0x00,      // ?
-                    0x80,      // Columns per row, 128 is full width
-                    0x00,      // ?
-                    0x02,      // Number of rows
-                    0x00,      // ?
+                    start as u8,         // Column offset
+                    0x00,                // ?
+                    first_row as u8,     // Row (a row is 8 pixels high)
+                    0x00,                // ?
+                    (end - start) as u8, // Columns per row, 128 is full width
+                    0x00,                // ?
+                    rows,                // Number of rows
+                    0x00,                // ?
                 ];
-                let x_offset = row * 128;
-                buffer.extend_from_slice(&self.display.data()[x_offset..(x_offset + 256)]);
-                self.device.write(buffer.as_slice())?;
+                for band in first_row..(first_row + rows as usize) {
+                    let offset = band * width;
+                    buffer.extend_from_slice(&self.display.data()[offset + start..offset + end]);
+                }
+                self.write(buffer.as_slice())?;
             }
+            self.metrics.frame_sent(Instant::now());
         }
         self.display.clear_dirty_flag();
 
         Ok(())
     }
 
+    /// Write a report to the device, checking the entire report was sent
+    fn write(&mut self, buffer: &[u8]) -> Result<(), Error> {
+        debug!(len = buffer.len(), "write report {:02x?}", buffer);
+        self.stats.written += 1;
+
+        let start = Instant::now();
+        let result = self.device.write(buffer);
+        self.metrics.hid_write.record(start.elapsed());
+
+        match result {
+            Ok(0) => Err(Error::WriteTimeout),
+            Ok(written) if written < buffer.len() => Err(Error::ShortWrite {
+                written,
+                expected: buffer.len(),
+            }),
+            Ok(_) => Ok(()),
+            Err(e) => {
+                warn!("write failed: {}", e);
+                Err(self.device_error(e))
+            }
+        }
+    }
+
+    ///
+    /// Classify an error from reading or writing the device
+    ///
+    /// hidapi does not report why a read/write failed, if the device no longer
+    /// answers a request for its product string it has been unplugged.
+    ///
+    fn device_error(&self, err: HidError) -> Error {
+        match self.device.get_product_string() {
+            Ok(_) => Error::HidAPI(err),
+            Err(_) => Error::Disconnected { source: err },
+        }
+    }
+
     /// Update LEDs if the array has been updated
     fn send_leds(&mut self) -> Result<(), Error> {
         if self.leds_dirty {
             let mut buffer: Vec<u8> = vec![LED_ADDR];
             buffer.extend_from_slice(&self.leds);
-            self.device.write(buffer.as_slice())?;
+            self.write(buffer.as_slice())?;
+            self.metrics.leds_sent(Instant::now());
         }
         self.leds_dirty = false;
 
@@ -176,13 +274,40 @@ impl MaschineMikroMk2 {
         for idx in 0..32 {
             let bytes_read = match self.device.read(&mut buffer) {
                 Ok(n) => n,
-                Err(e) => return Err(Error::HidAPI(e)),
+                Err(e) => {
+                    warn!("read failed: {}", e);
+                    return Err(self.device_error(e));
+                }
             };
+            if bytes_read == 0 {
+                continue;
+            }
 
-            if bytes_read > 0 && buffer[0] == 0x01 {
-                self.process_buttons(&buffer[1..6], context)?;
-            } else if (bytes_read > 0) && (buffer[0] == 0x20) && ((idx % 7) == 0) {
-                self.process_pads(&buffer[1..], context)?;
+            let report = &buffer[..bytes_read];
+            debug!(len = bytes_read, "read report {:02x?}", report);
+            self.stats.received += 1;
+            self.metrics.report_received(Instant::now());
+
+            let result = match report[0] {
+                0x01 => self.process_buttons(&report[1..], context),
+                0x20 if (idx % 7) == 0 => self.process_pads(&report[1..], context),
+                0x20 => {
+                    self.stats.dropped += 1;
+                    Ok(())
+                }
+                _ => Err(Error::InvalidReport),
+            };
+
+            // A bad report is not fatal, count it and carry on
+            if let Err(e) = result {
+                self.stats.invalid += 1;
+                warn!(
+                    report_id = report[0],
+                    len = bytes_read,
+                    invalid = self.stats.invalid,
+                    "{}",
+                    e
+                );
             }
         }
 
@@ -197,7 +322,7 @@ impl MaschineMikroMk2 {
 
         // Scan buttons
         for btn in BUTTON_SHIFT..BUTTON_NONE {
-            let button_pressed = is_button_pressed(&buffer, btn);
+            let button_pressed = is_button_pressed(buffer, btn);
             if button_pressed != self.button_states[btn as usize] {
                 self.button_states[btn as usize] = button_pressed;
 
@@ -271,19 +396,44 @@ impl MaschineMikroMk2 {
     fn set_led(&mut self, led: u8, colour: Colour) {
         let base = led as usize;
 
-        if self.is_rgb_led(led) {
+        let changed = if self.is_rgb_led(led) {
             let (r, g, b) = colour.components();
+            let (r, g, b) = (r >> 1, g >> 1, b >> 1);
 
-            self.leds_dirty =
+            let changed =
                 (r != self.leds[base]) | (g != self.leds[base + 1]) | (b != self.leds[base + 2]);
 
-            self.leds[base] = r >> 1;
-            self.leds[base + 1] = g >> 1;
-            self.leds[base + 2] = b >> 1;
+            self.leds[base] = r;
+            self.leds[base + 1] = g;
+            self.leds[base + 2] = b;
+            changed
         } else {
-            let m = colour.as_1bit();
-            self.leds_dirty = m != self.leds[base];
+            // Mono LEDs have a brightness level so they can be dimmed
+            let m = colour.brightness();
+            let changed = m != self.leds[base];
             self.leds[base] = m;
+            changed
+        };
+
+        if changed {
+            self.leds_dirty = true;
+            self.metrics.leds_changed();
+        }
+    }
+
+    /// Get the colour of an LED
+    fn led(&self, led: u8) -> Colour {
+        let base = led as usize;
+
+        if self.is_rgb_led(led) {
+            Colour::new(
+                self.leds[base] << 1,
+                self.leds[base + 1] << 1,
+                self.leds[base + 2] << 1,
+            )
+        } else {
+            let level = self.leds[base];
+            Colour::new(level, level, level)
         }
     }
 
@@ -387,22 +537,69 @@ impl MaschineMikroMk2 {
 
 impl Controller for MaschineMikroMk2 {
     fn set_button_led(&mut self, button: Button, colour: Colour) {
-        match self.button_to_led(button) {
-            Some(led) => self.set_led(led, colour),
-            None => (),
-        };
+        if let Some(led) = self.button_to_led(button) {
+            self.set_led(led, colour);
+        }
     }
 
     fn set_pad_led(&mut self, pad: u8, colour: Colour) {
-        match self.pad_to_led(pad) {
-            Some(led) => self.set_led(led, colour),
-            None => (),
-        };
+        if let Some(led) = self.pad_to_led(pad) {
+            self.set_led(led, colour);
+        }
+    }
+
+    fn display(&mut self) -> &mut MonochromeCanvas {
+        &mut self.display
+    }
+
+    fn device_info(&self) -> DeviceInfo {
+        self.device_info.clone()
+    }
+
+    fn flush(&mut self) -> Result<(), Error> {
+        self.send_frame()?;
+        self.send_leds()
+    }
+
+    fn led_snapshot(&self) -> LedSnapshot {
+        let mut snapshot = LedSnapshot::new();
+        for button in LED_BUTTONS.iter() {
+            if let Some(led) = self.button_to_led(*button) {
+                snapshot.set(Led::Button(*button), self.led(led));
+            }
+        }
+        for pad in 0..(PAD_COUNT as u8) {
+            if let Some(led) = self.pad_to_led(pad) {
+                snapshot.set(Led::Pad(pad), self.led(led));
+            }
+        }
+        snapshot
+    }
+
+    fn push_leds(&mut self) {
+        let snapshot = self.led_snapshot();
+        self.led_stack.push(snapshot);
+    }
+
+    fn pop_leds(&mut self) -> bool {
+        match self.led_stack.pop() {
+            Some(snapshot) => {
+                self.restore_leds(&snapshot);
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn metrics(&mut self) -> Option<&mut Metrics> {
+        Some(&mut self.metrics)
     }
 }
 
 impl EventTask for MaschineMikroMk2 {
     fn tick(&mut self, context: &mut EventContext) -> Result<(), Error> {
+        let _span = trace_span!("tick", state = self.tick_state).entered();
+
         if self.tick_state == 0 {
             self.send_frame()?;
         } else if self.tick_state == 1 {
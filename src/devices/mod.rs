@@ -1,27 +1,175 @@
-use hidapi::HidApi;
+#[cfg(feature = "hid")]
+pub use hidapi::HidApi;
+#[cfg(feature = "hid")]
+use hidapi::HidError;
+#[cfg(feature = "hid")]
+pub use maschine_mikro_mk2::{MaschineMikroMk2, ReportStats};
+#[cfg(feature = "simulator")]
+pub use simulator::Simulator;
 
-use crate::controller::Error;
-use maschine_mikro_mk2::MaschineMikroMk2;
+#[cfg(feature = "hid")]
+use crate::controller::{DeviceInfo, Error};
+#[cfg(feature = "hid")]
+use crate::diagnostics::{info, warn};
+use crate::events::Button;
+#[cfg(feature = "hid")]
+use alloc::format;
 
+#[cfg(feature = "hid")]
 mod maschine_mikro_mk2;
+#[cfg(feature = "simulator")]
+mod simulator;
 
+/// Buttons that have an LED on the Maschine Mikro Mk2
+pub(crate) const LED_BUTTONS: [Button; 27] = [
+    Button::Erase,
+    Button::Rec,
+    Button::Play,
+    Button::Grid,
+    Button::TransportRight,
+    Button::TransportLeft,
+    Button::Restart,
+    Button::NoteRepeat,
+    Button::Sampling,
+    Button::Browse,
+    Button::Group,
+    Button::Main,
+    Button::BrowseRight,
+    Button::BrowseLeft,
+    Button::Nav,
+    Button::Control,
+    Button::F3,
+    Button::F2,
+    Button::F1,
+    Button::Mute,
+    Button::Solo,
+    Button::Select,
+    Button::Duplicate,
+    Button::View,
+    Button::PadMode,
+    Button::Pattern,
+    Button::Scene,
+];
+
+#[cfg(feature = "hid")]
 pub fn get_device(hid_api: &HidApi) -> Result<MaschineMikroMk2, Error> {
-    let device = hid_api
-        .open(MaschineMikroMk2::VENDOR_ID, MaschineMikroMk2::PRODUCT_ID)
-        .expect("Cannot open device");
+    let (vendor_id, product_id) = (MaschineMikroMk2::VENDOR_ID, MaschineMikroMk2::PRODUCT_ID);
 
-    println!(
-        "Product: {}",
-        device.get_product_string()?.unwrap_or_default()
-    );
-    println!(
-        "Manufacturer: {}",
-        device.get_manufacturer_string()?.unwrap_or_default()
-    );
-    println!(
-        "Serial Number: {}",
-        device.get_serial_number_string()?.unwrap_or_default()
+    let info = hid_api
+        .device_list()
+        .find(|info| info.vendor_id() == vendor_id && info.product_id() == product_id);
+    let info = match info {
+        Some(info) => info,
+        None => {
+            // Report other devices from the same vendor as unsupported
+            let other = hid_api
+                .device_list()
+                .find(|info| info.vendor_id() == vendor_id);
+            return Err(match other {
+                Some(other) => Error::UnsupportedDevice {
+                    vendor_id,
+                    product_id: other.product_id(),
+                },
+                None => Error::DeviceNotFound {
+                    vendor_id,
+                    product_id,
+                },
+            });
+        }
+    };
+
+    let device = info.open_device(hid_api).map_err(|source| {
+        // hidapi reports the errno of the failed open() as the OS error
+        let os_error = std::io::Error::last_os_error();
+        warn!("unable to open device: {} ({})", source, os_error);
+        if is_permission_denied(&source, &os_error) {
+            Error::PermissionDenied {
+                vendor_id,
+                product_id,
+                source,
+            }
+        } else {
+            Error::OpenFailed {
+                vendor_id,
+                product_id,
+                source,
+            }
+        }
+    })?;
+
+    // Release number is BCD encoded, eg 0x0102 is version 1.02
+    let release = info.release_number();
+    let device_info = DeviceInfo {
+        product: device.get_product_string()?.unwrap_or_default(),
+        manufacturer: device.get_manufacturer_string()?.unwrap_or_default(),
+        serial: device.get_serial_number_string()?.unwrap_or_default(),
+        version: format!("{:x}.{:02x}", release >> 8, release & 0xFF),
+    };
+    info!(
+        product = %device_info.product,
+        manufacturer = %device_info.manufacturer,
+        serial = %device_info.serial,
+        version = %device_info.version,
+        "opened device"
     );
 
-    Ok(MaschineMikroMk2::new(device))
+    let mut ctlr = MaschineMikroMk2::new(device);
+    ctlr.set_device_info(device_info);
+    Ok(ctlr)
+}
+
+///
+/// Determine if opening a device failed due to a lack of permission rather than,
+/// for example, the device being in use by another application
+///
+#[cfg(feature = "hid")]
+fn is_permission_denied(error: &HidError, os_error: &std::io::Error) -> bool {
+    let message = format!("{}", error).to_lowercase();
+    message.contains("permission denied")
+        || message.contains("access denied")
+        || os_error.kind() == std::io::ErrorKind::PermissionDenied
+}
+
+#[cfg(all(test, feature = "hid"))]
+mod tests {
+    use super::*;
+    use alloc::string::{String, ToString};
+    use std::io;
+
+    fn hid_error(message: &str) -> HidError {
+        HidError::HidApiError {
+            message: String::from(message),
+        }
+    }
+
+    #[test]
+    fn permission_denied_only_when_the_error_says_so() {
+        let other = io::Error::from(io::ErrorKind::Other);
+        assert!(is_permission_denied(
+            &hid_error("Permission denied"),
+            &other
+        ));
+        assert!(is_permission_denied(
+            &hid_error("open failed"),
+            &io::Error::from(io::ErrorKind::PermissionDenied)
+        ));
+        assert!(!is_permission_denied(
+            &hid_error("Device or resource busy"),
+            &other
+        ));
+        assert!(!is_permission_denied(&HidError::HidApiErrorEmpty, &other));
+    }
+
+    #[test]
+    fn open_errors_keep_the_source() {
+        use std::error::Error as _;
+
+        let error = Error::OpenFailed {
+            vendor_id: 0x17cc,
+            product_id: 0x1200,
+            source: hid_error("Device or resource busy"),
+        };
+        assert!(error.to_string().contains("17cc:1200"));
+        assert!(error.source().is_some());
+    }
 }
@@ -0,0 +1,203 @@
+use alloc::collections::VecDeque;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use super::LED_BUTTONS;
+use crate::colour::Colour;
+use crate::controller::{Controller, DeviceInfo, Error, Led, LedSnapshot};
+use crate::diagnostics::trace;
+use crate::events::{Button, Event, EventContext, EventTask};
+use crate::gui::display::{Canvas, MonochromeCanvas};
+
+const PAD_COUNT: u8 = 16;
+
+///
+/// Software controller
+///
+/// Behaves like a Maschine Mikro Mk2 without requiring hardware, events are
+/// injected and LED/display state can be inspected. Useful for headless
+/// rendering and testing applications.
+///
+pub struct Simulator {
+    pub display: MonochromeCanvas,
+    leds: LedSnapshot,
+    led_stack: Vec<LedSnapshot>,
+    pending: VecDeque<Event>,
+    frames: usize,
+}
+
+impl Simulator {
+    pub fn new() -> Self {
+        let mut leds = LedSnapshot::new();
+        for button in LED_BUTTONS.iter() {
+            leds.set(Led::Button(*button), Colour::BLACK);
+        }
+        for pad in 0..PAD_COUNT {
+            leds.set(Led::Pad(pad), Colour::BLACK);
+        }
+
+        Simulator {
+            display: MonochromeCanvas::new(128, 64),
+            leds,
+            led_stack: Vec::new(),
+            pending: VecDeque::new(),
+            frames: 0,
+        }
+    }
+
+    ///
+    /// Queue an event to be generated on the next tick
+    ///
+    pub fn inject(&mut self, event: Event) {
+        trace!(?event, "inject");
+        self.pending.push_back(event);
+    }
+
+    ///
+    /// Current colour of a button LED
+    ///
+    pub fn button_led(&self, button: Button) -> Colour {
+        self.leds.get(Led::Button(button)).unwrap_or_default()
+    }
+
+    ///
+    /// Current colour of a pad LED
+    ///
+    pub fn pad_led(&self, pad: u8) -> Colour {
+        self.leds.get(Led::Pad(pad)).unwrap_or_default()
+    }
+
+    ///
+    /// Number of display frames that would have been sent to a device
+    ///
+    pub fn frames(&self) -> usize {
+        self.frames
+    }
+}
+
+impl Default for Simulator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Controller for Simulator {
+    fn set_button_led(&mut self, button: Button, colour: Colour) {
+        // Only buttons with an LED on the hardware, eg not the encoder
+        if LED_BUTTONS.contains(&button) {
+            self.leds.set(Led::Button(button), colour);
+        }
+    }
+
+    fn set_pad_led(&mut self, pad: u8, colour: Colour) {
+        if pad < PAD_COUNT {
+            self.leds.set(Led::Pad(pad), colour);
+        }
+    }
+
+    fn display(&mut self) -> &mut MonochromeCanvas {
+        &mut self.display
+    }
+
+    fn device_info(&self) -> DeviceInfo {
+        DeviceInfo {
+            product: String::from("Simulator"),
+            manufacturer: String::from("mm"),
+            serial: String::from("0"),
+            version: String::from(env!("CARGO_PKG_VERSION")),
+        }
+    }
+
+    fn flush(&mut self) -> Result<(), Error> {
+        if self.display.is_dirty() {
+            self.frames += 1;
+            self.display.clear_dirty_flag();
+        }
+        Ok(())
+    }
+
+    fn led_snapshot(&self) -> LedSnapshot {
+        self.leds.clone()
+    }
+
+    fn push_leds(&mut self) {
+        self.led_stack.push(self.leds.clone());
+    }
+
+    fn pop_leds(&mut self) -> bool {
+        match self.led_stack.pop() {
+            Some(snapshot) => {
+                self.restore_leds(&snapshot);
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+impl EventTask for Simulator {
+    fn tick(&mut self, context: &mut EventContext) -> Result<(), Error> {
+        self.flush()?;
+        while let Some(event) = self.pending.pop_front() {
+            context.add_event(event);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_led_starts_off() {
+        let sim = Simulator::new();
+        let snapshot = sim.led_snapshot();
+        for button in LED_BUTTONS.iter() {
+            assert_eq!(snapshot.get(Led::Button(*button)), Some(Colour::BLACK));
+        }
+        for pad in 0..PAD_COUNT {
+            assert_eq!(snapshot.get(Led::Pad(pad)), Some(Colour::BLACK));
+        }
+    }
+
+    #[test]
+    fn buttons_without_an_led_are_ignored() {
+        let mut sim = Simulator::new();
+        sim.set_button_led(Button::Play, Colour::WHITE);
+        sim.set_button_led(Button::MainEncoder, Colour::WHITE);
+        sim.set_button_led(Button::Unknown, Colour::WHITE);
+        sim.set_pad_led(PAD_COUNT, Colour::WHITE);
+
+        let snapshot = sim.led_snapshot();
+        assert_eq!(snapshot.get(Led::Button(Button::Play)), Some(Colour::WHITE));
+        assert_eq!(snapshot.get(Led::Button(Button::MainEncoder)), None);
+        assert_eq!(snapshot.get(Led::Button(Button::Unknown)), None);
+        assert_eq!(snapshot.get(Led::Pad(PAD_COUNT)), None);
+    }
+
+    #[test]
+    fn push_and_pop_nest() {
+        let mut sim = Simulator::new();
+        assert!(!sim.pop_leds());
+
+        sim.set_button_led(Button::Play, Colour::WHITE);
+        sim.push_leds();
+        sim.set_button_led(Button::Play, Colour::RED);
+        sim.set_pad_led(3, Colour::BLUE);
+        sim.push_leds();
+        sim.set_button_led(Button::Rec, Colour::WHITE);
+
+        assert!(sim.pop_leds());
+        assert_eq!(sim.button_led(Button::Play), Colour::RED);
+        assert_eq!(sim.button_led(Button::Rec), Colour::BLACK);
+        assert_eq!(sim.pad_led(3), Colour::BLUE);
+
+        assert!(sim.pop_leds());
+        assert_eq!(sim.button_led(Button::Play), Colour::WHITE);
+        assert_eq!(sim.pad_led(3), Colour::BLACK);
+
+        assert!(!sim.pop_leds());
+        assert_eq!(sim.button_led(Button::Play), Colour::WHITE);
+    }
+}
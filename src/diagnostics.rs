@@ -0,0 +1,45 @@
+///
+/// # Diagnostics
+///
+/// Logging macros used within the crate, forwarded to the `tracing` crate with
+/// the `std` feature and compiled out without it (`tracing` does not build for
+/// targets without atomic compare and swap, eg `thumbv6m-none-eabi`).
+///
+#[cfg(feature = "std")]
+#[allow(unused_imports)]
+pub(crate) use tracing::{debug, info, trace, trace_span, warn};
+
+#[cfg(not(feature = "std"))]
+macro_rules! disabled {
+    ($($arg:tt)*) => {};
+}
+
+#[cfg(not(feature = "std"))]
+#[allow(unused_macros)]
+macro_rules! disabled_span {
+    ($($arg:tt)*) => {
+        $crate::diagnostics::Span
+    };
+}
+
+#[cfg(not(feature = "std"))]
+#[allow(unused_imports)]
+pub(crate) use {
+    disabled as debug, disabled as info, disabled as trace, disabled as warn,
+    disabled_span as trace_span,
+};
+
+///
+/// Stands in for `tracing::Span` without the `std` feature
+///
+#[cfg(not(feature = "std"))]
+#[allow(dead_code)]
+pub(crate) struct Span;
+
+#[cfg(not(feature = "std"))]
+#[allow(dead_code)]
+impl Span {
+    pub(crate) fn entered(self) -> Self {
+        self
+    }
+}
@@ -1,4 +1,9 @@
+use alloc::boxed::Box;
+use alloc::string::String;
+#[cfg(feature = "hid")]
 use hidapi::HidError;
+#[cfg(feature = "png")]
+use png::{DecodingError, EncodingError};
 
 ///
 /// Common controller errors
@@ -6,31 +11,211 @@ use hidapi::HidError;
 #[derive(Debug)]
 #[allow(dead_code)]
 pub enum Error {
+    #[cfg(feature = "hid")]
     HidAPI(HidError),
 
+    /// Error reading a file
+    #[cfg(feature = "std")]
+    Io(std::io::Error),
+
+    /// Error decoding a PNG image
+    #[cfg(feature = "png")]
+    Png(DecodingError),
+
+    /// Error encoding a PNG image
+    #[cfg(feature = "png")]
+    PngEncoding(EncodingError),
+
+    /// No device with the vendor/product ID is connected
+    DeviceNotFound { vendor_id: u16, product_id: u16 },
+
+    /// Device was found but access was denied, usually a missing udev rule
+    #[cfg(feature = "hid")]
+    PermissionDenied {
+        vendor_id: u16,
+        product_id: u16,
+        source: HidError,
+    },
+
+    /// Device was found but could not be opened, eg it is in use by another
+    /// application
+    #[cfg(feature = "hid")]
+    OpenFailed {
+        vendor_id: u16,
+        product_id: u16,
+        source: HidError,
+    },
+
+    /// A device from a known vendor was found but is not supported
+    UnsupportedDevice { vendor_id: u16, product_id: u16 },
+
+    /// Device was unplugged, `source` is the error from the failed read/write
+    #[cfg(feature = "hid")]
+    Disconnected { source: HidError },
+
+    /// Fewer bytes were written to the device than requested
+    ShortWrite { written: usize, expected: usize },
+
+    /// Nothing could be written to the device
+    WriteTimeout,
+
     /// Input buffer does not container the expected amount of data.
     InvalidReport,
 
     /// Unexpected control returned from hardware device
     UnknownControl,
+
+    /// Colour could not be parsed
+    InvalidColour(String),
+
+    /// Image could not be decoded or uses an unsupported format
+    InvalidImage(String),
+
+    /// Config file could not be parsed
+    Config {
+        line: usize,
+        message: String,
+        source: Option<Box<Error>>,
+    },
 }
 
-impl std::fmt::Display for Error {
-    fn fmt(&self, fmt: &mut std::fmt::Formatter) -> std::fmt::Result {
-        match &*self {
+impl core::fmt::Display for Error {
+    fn fmt(&self, fmt: &mut core::fmt::Formatter) -> core::fmt::Result {
+        match self {
+            #[cfg(feature = "hid")]
             Error::HidAPI(e) => e.fmt(fmt), // Pass on to HIDAPI interface
+            #[cfg(feature = "std")]
+            Error::Io(e) => e.fmt(fmt),
+            #[cfg(feature = "png")]
+            Error::Png(e) => e.fmt(fmt),
+            #[cfg(feature = "png")]
+            Error::PngEncoding(e) => e.fmt(fmt),
+            Error::DeviceNotFound {
+                vendor_id,
+                product_id,
+            } => {
+                write!(
+                    fmt,
+                    "Device {:04x}:{:04x} not found, check it is connected",
+                    vendor_id, product_id
+                )
+            }
+            #[cfg(feature = "hid")]
+            Error::PermissionDenied {
+                vendor_id,
+                product_id,
+                ..
+            } => {
+                write!(
+                    fmt,
+                    "Permission denied opening device {:04x}:{:04x}, on Linux add the udev \
+                     rule described in the README and reconnect the device",
+                    vendor_id, product_id
+                )
+            }
+            #[cfg(feature = "hid")]
+            Error::OpenFailed {
+                vendor_id,
+                product_id,
+                source,
+            } => {
+                write!(
+                    fmt,
+                    "Unable to open device {:04x}:{:04x}, check it is not in use by another \
+                     application: {}",
+                    vendor_id, product_id, source
+                )
+            }
+            Error::UnsupportedDevice {
+                vendor_id,
+                product_id,
+            } => {
+                write!(
+                    fmt,
+                    "Device {:04x}:{:04x} is not supported",
+                    vendor_id, product_id
+                )
+            }
+            #[cfg(feature = "hid")]
+            Error::Disconnected { source } => {
+                write!(fmt, "Device was disconnected: {}", source)
+            }
+            Error::ShortWrite { written, expected } => {
+                write!(
+                    fmt,
+                    "Short write to device, {} of {} bytes written",
+                    written, expected
+                )
+            }
+            Error::WriteTimeout => {
+                write!(fmt, "Timed out writing to device")
+            }
             Error::InvalidReport => {
                 write!(fmt, "Report is either two small or not parsable")
             }
             Error::UnknownControl => {
                 write!(fmt, "Unexpected control returned from hardware device")
             }
+            Error::InvalidColour(value) => {
+                write!(fmt, "Invalid colour: {:?}", value)
+            }
+            Error::InvalidImage(message) => {
+                write!(fmt, "Invalid image: {}", message)
+            }
+            Error::Config { line, message, .. } => {
+                write!(fmt, "Invalid config on line {}: {}", line, message)
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            #[cfg(feature = "hid")]
+            Error::HidAPI(e) => Some(e),
+            #[cfg(feature = "hid")]
+            Error::Disconnected { source }
+            | Error::PermissionDenied { source, .. }
+            | Error::OpenFailed { source, .. } => Some(source),
+            Error::Io(e) => Some(e),
+            #[cfg(feature = "png")]
+            Error::Png(e) => Some(e),
+            #[cfg(feature = "png")]
+            Error::PngEncoding(e) => Some(e),
+            Error::Config {
+                source: Some(e), ..
+            } => Some(e.as_ref()),
+            _ => None,
         }
     }
 }
 
+#[cfg(feature = "hid")]
 impl From<HidError> for Error {
     fn from(err: HidError) -> Error {
         Error::HidAPI(err)
     }
 }
+
+#[cfg(feature = "std")]
+impl From<std::io::Error> for Error {
+    fn from(err: std::io::Error) -> Error {
+        Error::Io(err)
+    }
+}
+
+#[cfg(feature = "png")]
+impl From<DecodingError> for Error {
+    fn from(err: DecodingError) -> Error {
+        Error::Png(err)
+    }
+}
+
+#[cfg(feature = "png")]
+impl From<EncodingError> for Error {
+    fn from(err: EncodingError) -> Error {
+        Error::PngEncoding(err)
+    }
+}
@@ -0,0 +1,429 @@
+use super::{Button, Event, EventContext, EventHandler, EventKind, TimerId};
+use crate::diagnostics::trace;
+use alloc::rc::Rc;
+use alloc::vec::Vec;
+use core::cell::RefCell;
+
+///
+/// Identifier of a subscription, used to unsubscribe
+///
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct SubscriptionId(usize);
+
+///
+/// What happens after a handler has handled an event
+///
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Propagation {
+    /// Stop dispatching the event if the handler returns true
+    Consume,
+
+    /// Always pass the event on to lower priority handlers
+    PassThrough,
+}
+
+///
+/// Filter applied to events before they are passed to a handler
+///
+/// An empty filter matches every event, each list that is populated narrows the
+/// events that match. Button, encoder, pad and timer lists only apply to events
+/// of that kind.
+///
+#[derive(Clone, Debug, Default)]
+pub struct EventFilter {
+    kinds: Vec<EventKind>,
+    buttons: Vec<Button>,
+    encoders: Vec<u8>,
+    pads: Vec<u8>,
+    timers: Vec<TimerId>,
+}
+
+impl EventFilter {
+    ///
+    /// Filter that matches all events
+    ///
+    pub fn all() -> Self {
+        Default::default()
+    }
+
+    ///
+    /// Only match events of a particular kind
+    ///
+    pub fn kind(mut self, kind: EventKind) -> Self {
+        self.kinds.push(kind);
+        self
+    }
+
+    ///
+    /// Only match button events from particular buttons
+    ///
+    pub fn buttons(mut self, buttons: &[Button]) -> Self {
+        self.buttons.extend_from_slice(buttons);
+        self
+    }
+
+    ///
+    /// Only match encoder events from particular encoders
+    ///
+    pub fn encoders(mut self, encoders: &[u8]) -> Self {
+        self.encoders.extend_from_slice(encoders);
+        self
+    }
+
+    ///
+    /// Only match pad events from particular pads
+    ///
+    pub fn pads(mut self, pads: &[u8]) -> Self {
+        self.pads.extend_from_slice(pads);
+        self
+    }
+
+    ///
+    /// Only match timer events from particular timers
+    ///
+    pub fn timers(mut self, timers: &[TimerId]) -> Self {
+        self.timers.extend_from_slice(timers);
+        self
+    }
+
+    ///
+    /// Determine if an event matches the filter
+    ///
+    pub fn matches(&self, event: &Event) -> bool {
+        if !self.kinds.is_empty() && !self.kinds.contains(&event.kind()) {
+            return false;
+        }
+
+        match event {
+            Event::ButtonChange(button, _, _) => {
+                self.buttons.is_empty() || self.buttons.contains(button)
+            }
+            Event::EncoderChange(encoder, _, _) => {
+                self.encoders.is_empty() || self.encoders.contains(encoder)
+            }
+            Event::PadChange(pad, _, _) => self.pads.is_empty() || self.pads.contains(pad),
+            Event::Timer(timer) => self.timers.is_empty() || self.timers.contains(timer),
+        }
+    }
+}
+
+struct Subscription {
+    id: SubscriptionId,
+    filter: EventFilter,
+    priority: i32,
+    propagation: Propagation,
+    handler: Rc<RefCell<dyn EventHandler>>,
+}
+
+///
+/// Dispatches events to subscribed handlers
+///
+/// Handlers are called in priority order (highest first), handlers with the same
+/// priority are called in the order they subscribed.
+///
+/// Subscriptions cannot be changed during dispatch. Each handler is mutably
+/// borrowed while it handles an event, so a handler that reaches the bus (or
+/// itself) through a shared `RefCell` will panic with a `BorrowMutError`.
+/// Record the change in the handler and apply it once `dispatch` returns.
+///
+pub struct EventBus {
+    next_id: usize,
+    subscriptions: Vec<Subscription>,
+}
+
+impl EventBus {
+    pub fn new() -> Self {
+        EventBus {
+            next_id: 0,
+            subscriptions: Vec::new(),
+        }
+    }
+
+    ///
+    /// Subscribe a handler to events
+    ///
+    /// # Arguments
+    ///
+    /// * `handler` - Handler to receive events
+    /// * `filter` - Events the handler is interested in
+    /// * `priority` - Higher priority handlers receive events first
+    /// * `propagation` - If handled events are passed on to lower priority handlers
+    ///
+    pub fn subscribe(
+        &mut self,
+        handler: Rc<RefCell<dyn EventHandler>>,
+        filter: EventFilter,
+        priority: i32,
+        propagation: Propagation,
+    ) -> SubscriptionId {
+        let id = SubscriptionId(self.next_id);
+        self.next_id += 1;
+
+        // Insert after any subscriptions of the same or higher priority
+        let idx = self
+            .subscriptions
+            .iter()
+            .position(|s| s.priority < priority)
+            .unwrap_or(self.subscriptions.len());
+        self.subscriptions.insert(
+            idx,
+            Subscription {
+                id,
+                filter,
+                priority,
+                propagation,
+                handler,
+            },
+        );
+
+        id
+    }
+
+    ///
+    /// Remove a subscription, returns false if it did not exist
+    ///
+    pub fn unsubscribe(&mut self, id: SubscriptionId) -> bool {
+        let count = self.subscriptions.len();
+        self.subscriptions.retain(|s| s.id != id);
+        count != self.subscriptions.len()
+    }
+
+    /// Number of subscriptions
+    pub fn len(&self) -> usize {
+        self.subscriptions.len()
+    }
+
+    /// No handlers are subscribed
+    pub fn is_empty(&self) -> bool {
+        self.subscriptions.is_empty()
+    }
+
+    ///
+    /// Dispatch a single event, returns true if any handler handled it
+    ///
+    pub fn dispatch(&mut self, event: &Event) -> bool {
+        let mut handled = false;
+
+        for subscription in self.subscriptions.iter() {
+            if !subscription.filter.matches(event) {
+                continue;
+            }
+
+            let result = subscription.handler.borrow_mut().handle(event);
+            handled |= result;
+            if result && subscription.propagation == Propagation::Consume {
+                break;
+            }
+        }
+
+        trace!(?event, handled, "dispatched");
+        handled
+    }
+
+    ///
+    /// Dispatch all pending events in a context
+    ///
+    pub fn dispatch_all(&mut self, context: &mut EventContext) {
+        while let Some(event) = context.events.pop_front() {
+            self.dispatch(&event);
+        }
+    }
+}
+
+impl Default for EventBus {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events::Direction;
+    use alloc::vec;
+
+    /// Handler that records the events it sees in a shared log
+    struct Recorder {
+        name: &'static str,
+        handled: bool,
+        log: Rc<RefCell<Vec<&'static str>>>,
+    }
+
+    impl EventHandler for Recorder {
+        fn handle(&mut self, _: &Event) -> bool {
+            self.log.borrow_mut().push(self.name);
+            self.handled
+        }
+    }
+
+    fn recorder(
+        name: &'static str,
+        handled: bool,
+        log: &Rc<RefCell<Vec<&'static str>>>,
+    ) -> Rc<RefCell<Recorder>> {
+        Rc::new(RefCell::new(Recorder {
+            name,
+            handled,
+            log: log.clone(),
+        }))
+    }
+
+    fn press(button: Button) -> Event {
+        Event::ButtonChange(button, true, false)
+    }
+
+    #[test]
+    fn handlers_are_called_in_priority_order() {
+        let log = Rc::new(RefCell::new(Vec::new()));
+        let mut bus = EventBus::new();
+        let all = EventFilter::all;
+        bus.subscribe(
+            recorder("low", false, &log),
+            all(),
+            -1,
+            Propagation::PassThrough,
+        );
+        bus.subscribe(
+            recorder("first", false, &log),
+            all(),
+            5,
+            Propagation::PassThrough,
+        );
+        bus.subscribe(
+            recorder("high", false, &log),
+            all(),
+            10,
+            Propagation::PassThrough,
+        );
+        bus.subscribe(
+            recorder("second", false, &log),
+            all(),
+            5,
+            Propagation::PassThrough,
+        );
+
+        assert!(!bus.dispatch(&press(Button::Play)));
+        assert_eq!(*log.borrow(), vec!["high", "first", "second", "low"]);
+    }
+
+    #[test]
+    fn consume_stops_only_handled_events() {
+        let log = Rc::new(RefCell::new(Vec::new()));
+        let mut bus = EventBus::new();
+        let all = EventFilter::all;
+        bus.subscribe(
+            recorder("pass", true, &log),
+            all(),
+            3,
+            Propagation::PassThrough,
+        );
+        bus.subscribe(
+            recorder("ignore", false, &log),
+            all(),
+            2,
+            Propagation::Consume,
+        );
+        bus.subscribe(
+            recorder("consume", true, &log),
+            all(),
+            1,
+            Propagation::Consume,
+        );
+        bus.subscribe(
+            recorder("never", true, &log),
+            all(),
+            0,
+            Propagation::PassThrough,
+        );
+
+        assert!(bus.dispatch(&press(Button::Play)));
+        assert_eq!(*log.borrow(), vec!["pass", "ignore", "consume"]);
+    }
+
+    #[test]
+    fn filters_match_kinds_and_sources() {
+        let filter = EventFilter::all()
+            .kind(EventKind::Button)
+            .buttons(&[Button::Play]);
+        assert!(filter.matches(&press(Button::Play)));
+        assert!(!filter.matches(&press(Button::Rec)));
+        assert!(!filter.matches(&Event::PadChange(0, 100, false)));
+
+        // Source lists only narrow events of their own kind
+        let filter = EventFilter::all().pads(&[1, 2]).encoders(&[0]);
+        assert!(filter.matches(&Event::PadChange(2, 100, false)));
+        assert!(!filter.matches(&Event::PadChange(3, 100, false)));
+        assert!(filter.matches(&Event::EncoderChange(0, Direction::Up, false)));
+        assert!(!filter.matches(&Event::EncoderChange(1, Direction::Down, false)));
+        assert!(filter.matches(&press(Button::Rec)));
+
+        let filter = EventFilter::all()
+            .kind(EventKind::Pad)
+            .kind(EventKind::Encoder);
+        assert!(filter.matches(&Event::PadChange(0, 0, false)));
+        assert!(filter.matches(&Event::EncoderChange(0, Direction::Up, true)));
+        assert!(!filter.matches(&press(Button::Play)));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn filters_match_timers() {
+        let mut scheduler = crate::events::Scheduler::new();
+        let (tick, other) = (
+            scheduler.once(core::time::Duration::from_secs(1)),
+            scheduler.once(core::time::Duration::from_secs(1)),
+        );
+        let filter = EventFilter::all().timers(&[tick]);
+        assert!(filter.matches(&Event::Timer(tick)));
+        assert!(!filter.matches(&Event::Timer(other)));
+    }
+
+    #[test]
+    fn unsubscribed_handlers_receive_nothing() {
+        let log = Rc::new(RefCell::new(Vec::new()));
+        let mut bus = EventBus::new();
+        let all = EventFilter::all;
+        let id = bus.subscribe(recorder("gone", true, &log), all(), 1, Propagation::Consume);
+        bus.subscribe(recorder("kept", true, &log), all(), 0, Propagation::Consume);
+        assert_eq!(bus.len(), 2);
+
+        assert!(bus.unsubscribe(id));
+        assert!(!bus.unsubscribe(id));
+        assert_eq!(bus.len(), 1);
+
+        let mut context = EventContext::new();
+        context.add_event(press(Button::Play));
+        context.add_event(press(Button::Rec));
+        bus.dispatch_all(&mut context);
+        assert!(context.events.is_empty());
+        assert_eq!(*log.borrow(), vec!["kept", "kept"]);
+    }
+
+    /// Handler that tries to subscribe to the bus it is dispatched from
+    struct Subscriber {
+        bus: Rc<RefCell<EventBus>>,
+    }
+
+    impl EventHandler for Subscriber {
+        fn handle(&mut self, _: &Event) -> bool {
+            let log = Rc::new(RefCell::new(Vec::new()));
+            self.bus.borrow_mut().subscribe(
+                recorder("late", true, &log),
+                EventFilter::all(),
+                0,
+                Propagation::Consume,
+            );
+            true
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "already borrowed")]
+    fn changing_subscriptions_during_dispatch_panics() {
+        let bus = Rc::new(RefCell::new(EventBus::new()));
+        let handler = Rc::new(RefCell::new(Subscriber { bus: bus.clone() }));
+        bus.borrow_mut()
+            .subscribe(handler, EventFilter::all(), 0, Propagation::Consume);
+        bus.borrow_mut().dispatch(&press(Button::Play));
+    }
+}
@@ -1,11 +1,19 @@
 use crate::controller::Error;
-use std::collections::VecDeque;
+use alloc::collections::VecDeque;
+
+mod bus;
+mod scheduler;
+
+pub use bus::{EventBus, EventFilter, Propagation, SubscriptionId};
+#[cfg(feature = "std")]
+pub use scheduler::Scheduler;
+pub use scheduler::{Division, TimerId};
 
 ///
 /// System Events
 ///
 #[derive(Debug)]
-#[allow(dead_code)]
+#[allow(dead_code, clippy::enum_variant_names)]
 pub enum Event {
     ///
     /// Button change (Button, Pressed, Shift)
@@ -21,6 +29,36 @@ pub enum Event {
     /// Pad change (Pad Number, Velocity, Shift)
     ///
     PadChange(u8, u8, bool),
+
+    ///
+    /// Timer requested from the scheduler has expired (Timer)
+    ///
+    Timer(TimerId),
+}
+
+impl Event {
+    ///
+    /// Kind of the event
+    ///
+    pub fn kind(&self) -> EventKind {
+        match self {
+            Event::ButtonChange(..) => EventKind::Button,
+            Event::EncoderChange(..) => EventKind::Encoder,
+            Event::PadChange(..) => EventKind::Pad,
+            Event::Timer(..) => EventKind::Timer,
+        }
+    }
+}
+
+///
+/// Kind of an event, used for filtering
+///
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum EventKind {
+    Button,
+    Encoder,
+    Pad,
+    Timer,
 }
 
 ///
@@ -33,7 +71,7 @@ pub enum Direction {
     Down,
 }
 
-#[derive(Debug)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
 #[allow(dead_code)]
 pub enum Button {
     Erase,
@@ -86,6 +124,12 @@ impl EventContext {
     }
 }
 
+impl Default for EventContext {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 ///
 /// Generator for events
 ///
@@ -0,0 +1,405 @@
+#[cfg(feature = "std")]
+use super::{Event, EventContext, EventTask};
+#[cfg(feature = "std")]
+use crate::controller::Error;
+#[cfg(feature = "std")]
+use crate::diagnostics::trace;
+#[cfg(feature = "std")]
+use alloc::vec::Vec;
+use core::time::Duration;
+#[cfg(feature = "std")]
+use std::time::Instant;
+
+///
+/// Identifier of a timer, delivered with `Event::Timer`
+///
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct TimerId(usize);
+
+///
+/// Musical note division used for tempo synced timers
+///
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Division {
+    Bar,
+    Half,
+    Quarter,
+    Eighth,
+    Sixteenth,
+    ThirtySecond,
+    QuarterTriplet,
+    EighthTriplet,
+    SixteenthTriplet,
+}
+
+impl Division {
+    ///
+    /// Length of the division in beats (quarter notes), assumes 4/4
+    ///
+    pub fn beats(&self) -> f64 {
+        match self {
+            Division::Bar => 4.0,
+            Division::Half => 2.0,
+            Division::Quarter => 1.0,
+            Division::Eighth => 0.5,
+            Division::Sixteenth => 0.25,
+            Division::ThirtySecond => 0.125,
+            Division::QuarterTriplet => 2.0 / 3.0,
+            Division::EighthTriplet => 1.0 / 3.0,
+            Division::SixteenthTriplet => 1.0 / 6.0,
+        }
+    }
+
+    ///
+    /// Duration of the division at a tempo, `None` unless the tempo is a
+    /// positive finite number
+    ///
+    pub fn duration(&self, bpm: f64) -> Option<Duration> {
+        if !(bpm.is_finite() && bpm > 0.0) {
+            return None;
+        }
+        Duration::try_from_secs_f64((60.0 / bpm) * self.beats()).ok()
+    }
+}
+
+#[cfg(feature = "std")]
+enum Interval {
+    Once,
+    Fixed(Duration),
+    /// Due on the next multiple of the division on the beat grid
+    Synced {
+        division: Division,
+        beat: f64,
+    },
+}
+
+#[cfg(feature = "std")]
+struct Timer {
+    id: TimerId,
+    due: Instant,
+    interval: Interval,
+}
+
+///
+/// Scheduler for one-shot and repeating timers
+///
+/// Expired timers are added to the event context as `Event::Timer` each tick.
+/// Requires the `std` feature as a clock is required.
+///
+/// Tempo synced timers share a beat grid that starts when the scheduler is
+/// created (or restarted), so timers of the same division fire together
+/// whenever they were requested.
+///
+#[cfg(feature = "std")]
+pub struct Scheduler {
+    next_id: usize,
+    tempo: f64,
+    // Point on the beat grid the tempo applies from
+    anchor: (Instant, f64),
+    timers: Vec<Timer>,
+}
+
+#[cfg(feature = "std")]
+impl Scheduler {
+    pub const DEFAULT_TEMPO: f64 = 120.0;
+    pub const MIN_TEMPO: f64 = 1.0;
+    pub const MAX_TEMPO: f64 = 999.0;
+
+    pub fn new() -> Self {
+        Scheduler {
+            next_id: 0,
+            tempo: Self::DEFAULT_TEMPO,
+            anchor: (Instant::now(), 0.0),
+            timers: Vec::new(),
+        }
+    }
+
+    ///
+    /// Current tempo in beats per minute
+    ///
+    pub fn tempo(&self) -> f64 {
+        self.tempo
+    }
+
+    ///
+    /// Set the tempo in beats per minute, tempo synced timers are rescheduled
+    /// to the new tempo
+    ///
+    /// The tempo is clamped to `MIN_TEMPO..=MAX_TEMPO`, values that are not a
+    /// positive finite number are ignored.
+    ///
+    pub fn set_tempo(&mut self, bpm: f64) {
+        if bpm.is_finite() && bpm > 0.0 {
+            let now = Instant::now();
+            self.anchor = (now, self.beat_at(now));
+            self.tempo = bpm.clamp(Self::MIN_TEMPO, Self::MAX_TEMPO);
+            self.reschedule_synced();
+        }
+    }
+
+    ///
+    /// Current position on the beat grid in beats (quarter notes)
+    ///
+    pub fn beat(&self) -> f64 {
+        self.beat_at(Instant::now())
+    }
+
+    ///
+    /// Restart the beat grid from now, tempo synced timers are rescheduled to
+    /// the next division from now
+    ///
+    pub fn restart(&mut self) {
+        self.anchor = (Instant::now(), 0.0);
+        for timer in self.timers.iter_mut() {
+            if let Interval::Synced { division, beat } = &mut timer.interval {
+                *beat = division.beats();
+            }
+        }
+        self.reschedule_synced();
+    }
+
+    ///
+    /// Request a timer that fires once after a delay
+    ///
+    pub fn once(&mut self, delay: Duration) -> TimerId {
+        self.add(Instant::now() + delay, Interval::Once)
+    }
+
+    ///
+    /// Request a timer that fires repeatedly
+    ///
+    pub fn repeat(&mut self, interval: Duration) -> TimerId {
+        self.add(Instant::now() + interval, Interval::Fixed(interval))
+    }
+
+    ///
+    /// Request a repeating timer that fires on a musical division of the tempo
+    ///
+    pub fn synced(&mut self, division: Division) -> TimerId {
+        let beat = self.next_beat(division, Instant::now());
+        self.add(self.time_of(beat), Interval::Synced { division, beat })
+    }
+
+    ///
+    /// Cancel a timer, returns false if the timer did not exist (or has fired)
+    ///
+    pub fn cancel(&mut self, id: TimerId) -> bool {
+        let count = self.timers.len();
+        self.timers.retain(|t| t.id != id);
+        count != self.timers.len()
+    }
+
+    /// Number of active timers
+    pub fn len(&self) -> usize {
+        self.timers.len()
+    }
+
+    /// No timers are active
+    pub fn is_empty(&self) -> bool {
+        self.timers.is_empty()
+    }
+
+    /// Position on the beat grid at a point in time
+    fn beat_at(&self, time: Instant) -> f64 {
+        let (anchor_time, anchor_beat) = self.anchor;
+        let elapsed = time.saturating_duration_since(anchor_time).as_secs_f64();
+        anchor_beat + elapsed * self.tempo / 60.0
+    }
+
+    /// Point in time of a position on the beat grid
+    fn time_of(&self, beat: f64) -> Instant {
+        let (anchor_time, anchor_beat) = self.anchor;
+        let offset = (beat - anchor_beat).max(0.0) * 60.0 / self.tempo;
+        anchor_time + Duration::from_secs_f64(offset)
+    }
+
+    /// Next multiple of the division on the beat grid after a point in time
+    fn next_beat(&self, division: Division, time: Instant) -> f64 {
+        let step = division.beats();
+        ((self.beat_at(time) / step).floor() + 1.0) * step
+    }
+
+    /// Update the due time of tempo synced timers from their beat
+    fn reschedule_synced(&mut self) {
+        let mut timers = core::mem::take(&mut self.timers);
+        for timer in timers.iter_mut() {
+            if let Interval::Synced { beat, .. } = timer.interval {
+                timer.due = self.time_of(beat);
+            }
+        }
+        self.timers = timers;
+    }
+
+    /// Fire expired timers and schedule their next interval
+    fn tick_at(&mut self, now: Instant, context: &mut EventContext) {
+        let mut timers = core::mem::take(&mut self.timers);
+
+        timers.retain_mut(|timer| {
+            if timer.due > now {
+                return true;
+            }
+            trace!(timer = ?timer.id, "timer fired");
+            context.add_event(Event::Timer(timer.id));
+
+            match &mut timer.interval {
+                Interval::Once => return false,
+                Interval::Fixed(interval) => {
+                    // Schedule from the previous due time to avoid drift,
+                    // unless we have fallen more than an interval behind.
+                    timer.due += *interval;
+                    if timer.due <= now {
+                        timer.due = now + *interval;
+                    }
+                }
+                Interval::Synced { division, beat } => {
+                    // Skip any divisions that have been missed
+                    *beat += division.beats();
+                    if self.time_of(*beat) <= now {
+                        *beat = self.next_beat(*division, now);
+                    }
+                    timer.due = self.time_of(*beat);
+                }
+            }
+            true
+        });
+
+        self.timers = timers;
+    }
+
+    fn add(&mut self, due: Instant, interval: Interval) -> TimerId {
+        let id = TimerId(self.next_id);
+        self.next_id += 1;
+
+        self.timers.push(Timer { id, due, interval });
+
+        id
+    }
+}
+
+#[cfg(feature = "std")]
+impl Default for Scheduler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(feature = "std")]
+impl EventTask for Scheduler {
+    fn tick(&mut self, context: &mut EventContext) -> Result<(), Error> {
+        self.tick_at(Instant::now(), context);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(feature = "std")]
+    use alloc::vec;
+
+    #[test]
+    fn division_duration() {
+        assert_eq!(
+            Division::Quarter.duration(120.0),
+            Some(Duration::from_millis(500))
+        );
+        assert_eq!(Division::Bar.duration(60.0), Some(Duration::from_secs(4)));
+        assert_eq!(Division::Quarter.duration(0.0), None);
+        assert_eq!(Division::Quarter.duration(-120.0), None);
+        assert_eq!(Division::Quarter.duration(f64::NAN), None);
+        assert_eq!(Division::Quarter.duration(f64::INFINITY), None);
+        assert_eq!(Division::Bar.duration(f64::MIN_POSITIVE), None);
+    }
+
+    #[cfg(feature = "std")]
+    fn fired(scheduler: &mut Scheduler, now: Instant) -> Vec<TimerId> {
+        let mut context = EventContext::new();
+        scheduler.tick_at(now, &mut context);
+        context
+            .events
+            .into_iter()
+            .filter_map(|event| match event {
+                Event::Timer(id) => Some(id),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn invalid_tempo_is_ignored_or_clamped() {
+        let mut scheduler = Scheduler::new();
+        scheduler.set_tempo(f64::INFINITY);
+        scheduler.set_tempo(f64::NAN);
+        scheduler.set_tempo(0.0);
+        assert_eq!(scheduler.tempo(), Scheduler::DEFAULT_TEMPO);
+
+        scheduler.set_tempo(1e9);
+        assert_eq!(scheduler.tempo(), Scheduler::MAX_TEMPO);
+        scheduler.set_tempo(1e-9);
+        assert_eq!(scheduler.tempo(), Scheduler::MIN_TEMPO);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn one_shot_and_repeating_timers() {
+        let mut scheduler = Scheduler::new();
+        let start = Instant::now();
+        let once = scheduler.once(Duration::from_millis(10));
+        let repeat = scheduler.repeat(Duration::from_millis(20));
+
+        let at = |ms| start + Duration::from_millis(ms);
+        assert_eq!(fired(&mut scheduler, at(0)), vec![]);
+        assert_eq!(fired(&mut scheduler, at(15)), vec![once]);
+        assert_eq!(fired(&mut scheduler, at(25)), vec![repeat]);
+        assert_eq!(fired(&mut scheduler, at(45)), vec![repeat]);
+        assert_eq!(scheduler.len(), 1);
+
+        assert!(scheduler.cancel(repeat));
+        assert!(!scheduler.cancel(once));
+        assert!(scheduler.is_empty());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn synced_timers_share_the_beat_grid() {
+        let mut scheduler = Scheduler::new();
+        let start = Instant::now();
+        scheduler.anchor = (start, 0.0);
+        let at = |ms| start + Duration::from_millis(ms);
+
+        // 120bpm, a beat every 500ms. Both timers are due on beat 1 even
+        // though they were requested at different times.
+        let first = scheduler.synced(Division::Quarter);
+        let second = scheduler.synced(Division::Quarter);
+        let half = scheduler.synced(Division::Eighth);
+
+        assert_eq!(fired(&mut scheduler, at(260)), vec![half]);
+        assert_eq!(fired(&mut scheduler, at(510)), vec![first, second, half]);
+        assert!((scheduler.beat_at(at(1000)) - 2.0).abs() < 1e-9);
+
+        // Missed divisions are skipped
+        assert_eq!(fired(&mut scheduler, at(2100)), vec![first, second, half]);
+        assert_eq!(fired(&mut scheduler, at(2300)), vec![half]);
+        assert_eq!(fired(&mut scheduler, at(2510)), vec![first, second, half]);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn tempo_change_keeps_the_beat_position() {
+        let mut scheduler = Scheduler::new();
+        let start = Instant::now();
+        scheduler.anchor = (start, 0.0);
+        let at = |ms| start + Duration::from_millis(ms);
+        let timer = scheduler.synced(Division::Bar);
+
+        // Two beats in at 120bpm then halve the tempo, the remaining two beats
+        // of the bar now take two seconds.
+        scheduler.anchor = (at(1000), scheduler.beat_at(at(1000)));
+        scheduler.tempo = 60.0;
+        scheduler.reschedule_synced();
+
+        assert_eq!(fired(&mut scheduler, at(2900)), vec![]);
+        assert_eq!(fired(&mut scheduler, at(3010)), vec![timer]);
+    }
+}
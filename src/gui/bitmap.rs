@@ -0,0 +1,238 @@
+///
+/// # Bitmaps
+///
+/// Monochrome images (icons, sprites, the logo) in the same vertical byte format
+/// as the display that can be blitted onto a `Canvas`.
+///
+use alloc::borrow::Cow;
+
+///
+/// How the pixels of a bitmap are combined with the canvas
+///
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum BlitMode {
+    /// Replace the canvas with the bitmap
+    #[default]
+    Copy,
+
+    /// Set pixels that are set in the bitmap
+    Or,
+
+    /// Clear pixels that are clear in the bitmap
+    And,
+
+    /// Invert pixels that are set in the bitmap
+    Xor,
+
+    /// Replace the canvas where the mask is set, bitmaps without a mask use
+    /// their own set pixels as the mask (making clear pixels transparent)
+    Masked,
+}
+
+impl BlitMode {
+    ///
+    /// Combine a byte of the canvas with a byte of the bitmap, only bits set in
+    /// `mask` are changed
+    ///
+    pub fn apply(&self, canvas: u8, bitmap: u8, mask: u8) -> u8 {
+        match self {
+            BlitMode::Copy | BlitMode::Masked => (canvas & !mask) | (bitmap & mask),
+            BlitMode::Or => canvas | (bitmap & mask),
+            BlitMode::And => canvas & (bitmap | !mask),
+            BlitMode::Xor => canvas ^ (bitmap & mask),
+        }
+    }
+}
+
+///
+/// Monochrome image with an optional mask
+///
+/// Each byte is a column of 8 pixels (least significant bit at the top) with a
+/// band of `width` bytes for each 8 pixels of height, the last band is partially
+/// used if the height is not a multiple of 8. The mask uses the same layout.
+///
+#[derive(Clone, Debug)]
+pub struct Bitmap<'a> {
+    width: usize,
+    height: usize,
+    data: Cow<'a, [u8]>,
+    mask: Option<Cow<'a, [u8]>>,
+}
+
+impl<'a> Bitmap<'a> {
+    ///
+    /// Create a bitmap from either borrowed (e.g. a `static` table) or owned data
+    ///
+    pub fn new(width: usize, height: usize, data: impl Into<Cow<'a, [u8]>>) -> Self {
+        let data = data.into();
+        let size = Self::data_size(width, height);
+        if data.len() != size {
+            panic!("Bitmap data must be {} bytes long", size)
+        }
+
+        Bitmap {
+            width,
+            height,
+            data,
+            mask: None,
+        }
+    }
+
+    ///
+    /// Number of bytes used for a bitmap of a particular size
+    ///
+    pub fn data_size(width: usize, height: usize) -> usize {
+        width * height.div_ceil(8)
+    }
+
+    ///
+    /// Set the mask used by `BlitMode::Masked`, only pixels set in the mask are drawn
+    ///
+    pub fn set_mask(&mut self, mask: impl Into<Cow<'a, [u8]>>) {
+        let mask = mask.into();
+        if mask.len() != self.data.len() {
+            panic!("Bitmap mask must be {} bytes long", self.data.len())
+        }
+        self.mask = Some(mask);
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn data(&self) -> &[u8] {
+        &self.data
+    }
+
+    pub fn mask(&self) -> Option<&[u8]> {
+        self.mask.as_deref()
+    }
+
+    ///
+    /// Determine if a pixel is set
+    ///
+    pub fn pixel(&self, x: usize, y: usize) -> bool {
+        x < self.width
+            && y < self.height
+            && (self.data[(y >> 3) * self.width + x] >> (y & 7)) & 0x01 != 0
+    }
+
+    ///
+    /// Byte of the bitmap and the bits of it to draw with a particular mode
+    ///
+    pub(crate) fn column(&self, x: usize, band: usize, mode: BlitMode) -> (u8, u8) {
+        let idx = band * self.width + x;
+        let rows = core::cmp::min(8, self.height - band * 8);
+        let valid = 0xFFu8 >> (8 - rows);
+        let byte = self.data[idx];
+        let mask = match (mode, &self.mask) {
+            (BlitMode::Masked, Some(mask)) => mask[idx],
+            (BlitMode::Masked, None) => byte,
+            _ => 0xFF,
+        };
+        (byte, mask & valid)
+    }
+
+    ///
+    /// Copy of the bitmap that owns its data
+    ///
+    pub fn into_owned(self) -> Bitmap<'static> {
+        Bitmap {
+            width: self.width,
+            height: self.height,
+            data: Cow::Owned(self.data.into_owned()),
+            mask: self.mask.map(|mask| Cow::Owned(mask.into_owned())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gui::display::{Canvas, MonochromeCanvas, Pixel};
+    use alloc::vec;
+
+    fn is_on(canvas: &MonochromeCanvas, x: usize, y: usize) -> bool {
+        matches!(canvas.pixel(x, y), Some(Pixel::On))
+    }
+
+    /// Canvas with the left half of the top band set
+    fn half_set() -> MonochromeCanvas {
+        let mut canvas = MonochromeCanvas::new(8, 16);
+        canvas.fill_rect(0, 0, 4, 8, Pixel::On);
+        canvas
+    }
+
+    #[test]
+    fn apply_modes() {
+        assert_eq!(BlitMode::Copy.apply(0b1100, 0b1010, 0xFF), 0b1010);
+        assert_eq!(BlitMode::Or.apply(0b1100, 0b1010, 0xFF), 0b1110);
+        assert_eq!(BlitMode::And.apply(0b1100, 0b1010, 0xFF), 0b1000);
+        assert_eq!(BlitMode::Xor.apply(0b1100, 0b1010, 0xFF), 0b0110);
+        assert_eq!(BlitMode::Copy.apply(0b1100, 0b1010, 0b0011), 0b1110);
+        assert_eq!(BlitMode::And.apply(0b1100, 0b0000, 0b0011), 0b1100);
+    }
+
+    #[test]
+    fn pixels_and_partial_bands() {
+        let bitmap = Bitmap::new(2, 3, vec![0b101, 0b010]);
+        assert!(bitmap.pixel(0, 0) && bitmap.pixel(0, 2) && bitmap.pixel(1, 1));
+        assert!(!bitmap.pixel(1, 0) && !bitmap.pixel(2, 0) && !bitmap.pixel(0, 3));
+        assert_eq!(bitmap.column(0, 0, BlitMode::Copy), (0b101, 0b111));
+        assert_eq!(bitmap.column(0, 0, BlitMode::Masked), (0b101, 0b101));
+    }
+
+    #[test]
+    fn blit_modes() {
+        // Vertical stripes, columns 0, 2, 4 and 6 set
+        let stripes = Bitmap::new(8, 8, vec![0xFF, 0, 0xFF, 0, 0xFF, 0, 0xFF, 0]);
+
+        let mut canvas = half_set();
+        canvas.blit(&stripes, 0, 0, BlitMode::Copy);
+        assert!(is_on(&canvas, 0, 0) && !is_on(&canvas, 1, 0) && is_on(&canvas, 6, 0));
+
+        let mut canvas = half_set();
+        canvas.blit(&stripes, 0, 0, BlitMode::Or);
+        assert!(is_on(&canvas, 1, 0) && is_on(&canvas, 6, 0) && !is_on(&canvas, 7, 0));
+
+        let mut canvas = half_set();
+        canvas.blit(&stripes, 0, 0, BlitMode::And);
+        assert!(is_on(&canvas, 0, 0) && !is_on(&canvas, 1, 0) && !is_on(&canvas, 6, 0));
+
+        let mut canvas = half_set();
+        canvas.blit(&stripes, 0, 0, BlitMode::Xor);
+        assert!(!is_on(&canvas, 0, 0) && is_on(&canvas, 1, 0) && is_on(&canvas, 6, 0));
+
+        // Clear pixels are transparent without a mask
+        let mut canvas = half_set();
+        canvas.blit(&stripes, 0, 0, BlitMode::Masked);
+        assert!(is_on(&canvas, 1, 0) && is_on(&canvas, 6, 0) && !is_on(&canvas, 7, 0));
+
+        // Only the masked columns are replaced
+        let mut masked = stripes.clone();
+        masked.set_mask(vec![0, 0xFF, 0, 0, 0, 0, 0, 0]);
+        let mut canvas = half_set();
+        canvas.blit(&masked, 0, 0, BlitMode::Masked);
+        assert!(is_on(&canvas, 0, 0) && !is_on(&canvas, 1, 0) && is_on(&canvas, 2, 0));
+    }
+
+    #[test]
+    fn blit_across_bands_and_edges() {
+        let block = Bitmap::new(2, 2, vec![0b11, 0b11]);
+        let mut canvas = MonochromeCanvas::new(8, 16);
+        canvas.blit(&block, 3, 7, BlitMode::Copy);
+        assert!(is_on(&canvas, 3, 7) && is_on(&canvas, 4, 8));
+        assert!(!is_on(&canvas, 3, 6) && !is_on(&canvas, 3, 9));
+
+        // Partly outside the canvas is clipped
+        let mut canvas = MonochromeCanvas::new(8, 8);
+        canvas.blit(&block, -1, -1, BlitMode::Copy);
+        canvas.blit(&block, 7, 7, BlitMode::Copy);
+        assert!(is_on(&canvas, 0, 0) && is_on(&canvas, 7, 7));
+        assert!(!is_on(&canvas, 1, 1) && !is_on(&canvas, 6, 6));
+    }
+}
@@ -1,9 +1,18 @@
 ///
 /// # Display interface
 ///
-use super::font::FONT_NX5;
+use super::bitmap::{Bitmap, BlitMode};
+use super::font::Font;
+use super::text::{measure, truncate, Align};
+use super::viewport::Viewport;
 use crate::events::Direction;
-use std::cmp::{max, min};
+#[cfg(feature = "std")]
+use crate::Error;
+use alloc::format;
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::cmp::{max, min};
 
 ///
 /// State of a pixel
@@ -28,16 +37,6 @@ pub trait Canvas<T: Clone> {
     ///
     fn height(&self) -> usize;
 
-    ///
-    /// Get display data
-    ///
-    fn data_size(&self) -> usize;
-
-    ///
-    /// Get display data
-    ///
-    fn data(&self) -> &[u8];
-
     ///
     /// Data is dirty (has changed since last clear)
     ///
@@ -87,33 +86,123 @@ pub trait Canvas<T: Clone> {
     fn pixel(&self, x: usize, y: usize) -> Option<T>;
 
     ///
-    /// Copy canvas
+    /// Draw a horizontal line `w` pixels wide, clipped to the canvas
+    ///
+    fn draw_hline(&mut self, x: i32, y: i32, w: i32, colour: T) {
+        if y < 0 || y >= self.height() as i32 {
+            return;
+        }
+        for x in x.max(0)..(x + w).min(self.width() as i32) {
+            self.set_pixel(x as usize, y as usize, colour.clone());
+        }
+    }
+
+    ///
+    /// Draw a vertical line `h` pixels high, clipped to the canvas
+    ///
+    fn draw_vline(&mut self, x: i32, y: i32, h: i32, colour: T) {
+        if x < 0 || x >= self.width() as i32 {
+            return;
+        }
+        for y in y.max(0)..(y + h).min(self.height() as i32) {
+            self.set_pixel(x as usize, y as usize, colour.clone());
+        }
+    }
+
+    ///
+    /// Fill a rectangle, clipped to the canvas
+    ///
+    fn fill_rect(&mut self, x: i32, y: i32, w: i32, h: i32, colour: T) {
+        for x in x..(x + w) {
+            self.draw_vline(x, y, h, colour.clone());
+        }
+    }
+
+    ///
+    /// Copy the top left of a canvas, clipped to the smaller of the two canvases
     ///
     fn copy_from(&mut self, canvas: &dyn Canvas<T>);
 
+    ///
+    /// Draw a bitmap with its top left corner at (x, y), anything outside of the
+    /// canvas is clipped
+    ///
+    fn blit(&mut self, bitmap: &Bitmap, x: i32, y: i32, mode: BlitMode);
+
     ///
     /// Print, handles newlines but not scrolling
     ///
-    fn print(&mut self, s: &str, row: usize, col: usize, colour: T) {
+    /// Each line of text takes as many rows as the font needs (see `text_rows`),
+    /// a single row for fonts up to 7 pixels high.
+    ///
+    fn print(&mut self, s: &str, row: usize, col: usize, font: &dyn Font, colour: T) {
         let mut row = row;
         let mut col = col;
         for c in s.chars() {
             match c {
                 '\n' => {
-                    row += 1;
+                    row += text_rows(font);
                     col = 0;
                 }
                 _ => {
-                    col += self.print_char(c, row, col, colour.clone()) + 1;
+                    self.print_char(c, row, col, font, colour.clone());
+                    col += font.advance(c);
                 }
             }
         }
     }
 
     ///
-    /// Print character
+    /// Print a single line of text, clipped to `width` pixels
+    ///
+    /// Characters that do not completely fit are not printed, canvases that can
+    /// print part of a character should override this.
+    ///
+    fn print_clipped(
+        &mut self,
+        s: &str,
+        row: usize,
+        col: usize,
+        width: usize,
+        font: &dyn Font,
+        colour: T,
+    ) {
+        let end = col + width;
+        let mut col = col;
+        for c in s.chars().take_while(|&c| c != '\n') {
+            let advance = font.advance(c);
+            if col + advance.saturating_sub(1) > end {
+                break;
+            }
+            self.print_char(c, row, col, font, colour.clone());
+            col += advance;
+        }
+    }
+
+    ///
+    /// Print a single line of text aligned within a box `width` pixels wide
     ///
-    fn print_char(&mut self, t: char, row: usize, col: usize, colour: T) -> usize;
+    /// Text too wide for the box is truncated with an ellipsis.
+    ///
+    #[allow(clippy::too_many_arguments)]
+    fn print_aligned(
+        &mut self,
+        s: &str,
+        row: usize,
+        col: usize,
+        width: usize,
+        font: &dyn Font,
+        align: Align,
+        colour: T,
+    ) {
+        let text = truncate(s, font, width);
+        let offset = align.offset(measure(&text, font), width);
+        self.print_clipped(&text, row, col + offset, width - offset, font, colour);
+    }
+    ///
+    /// Print character, returns the width of the glyph
+    ///
+    fn print_char(&mut self, t: char, row: usize, col: usize, font: &dyn Font, colour: T) -> usize;
 
     ///
     /// Vertical scroll the rows in a particular direction
@@ -121,16 +210,85 @@ pub trait Canvas<T: Clone> {
     fn vscroll_rows(&mut self, row_start: usize, row_end: usize, direction: Direction);
 }
 
+///
+/// Number of rows (bands of 8 pixels) taken by a line of text printed in a font,
+/// including a line of spacing below the font
+///
+pub fn text_rows(font: &dyn Font) -> usize {
+    (font.height() + 8) / 8
+}
+
+///
+/// Rectangle in pixels
+///
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct Rect {
+    pub x: usize,
+    pub y: usize,
+    pub width: usize,
+    pub height: usize,
+}
+
+impl Rect {
+    pub fn new(x: usize, y: usize, width: usize, height: usize) -> Self {
+        Rect {
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+
+    ///
+    /// Area covered by both rectangles, empty if they do not overlap
+    ///
+    pub fn intersect(&self, other: &Rect) -> Rect {
+        let x = max(self.x, other.x);
+        let y = max(self.y, other.y);
+        let right = min(self.x + self.width, other.x + other.width);
+        let bottom = min(self.y + self.height, other.y + other.height);
+        Rect::new(x, y, right.saturating_sub(x), bottom.saturating_sub(y))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.width == 0 || self.height == 0
+    }
+
+    ///
+    /// Determine if a pixel is inside the rectangle
+    ///
+    pub fn contains(&self, x: usize, y: usize) -> bool {
+        (self.x..self.x + self.width).contains(&x) && (self.y..self.y + self.height).contains(&y)
+    }
+
+    /// Bits of a row band (8 pixels high) covered by the rectangle
+    fn band_mask(&self, row: usize) -> u8 {
+        let top = max(self.y, row * 8);
+        let bottom = min(self.y + self.height, row * 8 + 8);
+        if top >= bottom {
+            return 0;
+        }
+        (0xFFu16 >> (8 - (bottom - top)) << (top - row * 8)) as u8
+    }
+}
+
 ///
 /// Monochrome display that uses 1bpp for data display.
 ///
 /// Optimally the display width is a multiple of 8.
 ///
+/// Changes are tracked per row band (8 pixels high) as the range of columns that
+/// have been modified, allowing a driver to only transmit the changed regions.
+///
+/// Drawing is limited to the clip rectangle at the top of the clip stack (the
+/// whole canvas if the stack is empty), see `push_clip` and `Viewport`.
+///
 pub struct MonochromeCanvas {
     width: usize,
     height: usize,
     buffer: Vec<u8>,
-    dirty: bool,
+    dirty: Vec<Option<(usize, usize)>>,
+    clip: Vec<Rect>,
 }
 
 impl MonochromeCanvas {
@@ -139,7 +297,8 @@ impl MonochromeCanvas {
             width,
             height,
             buffer: vec![0; (width * height) / 8],
-            dirty: true,
+            dirty: vec![Some((0, width)); height / 8],
+            clip: Vec::new(),
         }
     }
 
@@ -153,8 +312,258 @@ impl MonochromeCanvas {
             width,
             height,
             buffer: buffer.to_vec(),
-            dirty: true,
+            dirty: vec![Some((0, width)); height / 8],
+            clip: Vec::new(),
+        }
+    }
+
+    ///
+    /// Size of the display data in bytes
+    ///
+    pub fn data_size(&self) -> usize {
+        self.buffer.len()
+    }
+
+    ///
+    /// Display data, a band of `width` bytes for each row (8 pixels high)
+    ///
+    pub fn data(&self) -> &[u8] {
+        self.buffer.as_slice()
+    }
+
+    ///
+    /// Range of columns (start, end) changed in a row band since the dirty flag
+    /// was last cleared
+    ///
+    pub fn dirty_band(&self, row: usize) -> Option<(usize, usize)> {
+        self.dirty.get(row).copied().flatten()
+    }
+
+    ///
+    /// Mark a range of columns in a row band as changed
+    ///
+    pub fn mark_dirty(&mut self, row: usize, start_col: usize, end_col: usize) {
+        let end_col = min(end_col, self.width);
+        if start_col >= end_col {
+            return;
+        }
+        if let Some(band) = self.dirty.get_mut(row) {
+            *band = Some(match *band {
+                Some((start, end)) => (min(start, start_col), max(end, end_col)),
+                None => (start_col, end_col),
+            });
+        }
+    }
+
+    ///
+    /// Limit drawing to a rectangle (within the current clip rectangle) until
+    /// `pop_clip` is called
+    ///
+    pub fn push_clip(&mut self, rect: Rect) {
+        let clip = self.clip().intersect(&rect);
+        self.clip.push(clip);
+    }
+
+    ///
+    /// Restore the clip rectangle from before the last `push_clip`
+    ///
+    pub fn pop_clip(&mut self) {
+        self.clip.pop();
+    }
+
+    ///
+    /// Current clip rectangle, drawing outside of it has no effect
+    ///
+    pub fn clip(&self) -> Rect {
+        self.clip
+            .last()
+            .copied()
+            .unwrap_or(Rect::new(0, 0, self.width, self.height))
+    }
+
+    ///
+    /// Sub-canvas covering part of this canvas, see `Viewport::new`
+    ///
+    pub fn viewport(&mut self, row: usize, col: usize, rows: usize, width: usize) -> Viewport<'_> {
+        Viewport::new(self, row, col, rows, width)
+    }
+
+    ///
+    /// Update the bytes of a row band from `start_col` up to `end_col`, only the
+    /// bits in `mask` that are inside the clip rectangle are changed
+    ///
+    /// `update` is called with the column and current byte and returns the new byte.
+    /// Only the columns whose bytes change are marked as dirty.
+    ///
+    fn update_band<F: FnMut(usize, u8) -> u8>(
+        &mut self,
+        row: usize,
+        start_col: usize,
+        end_col: usize,
+        mask: u8,
+        mut update: F,
+    ) {
+        let clip = self.clip();
+        let start_col = max(start_col, clip.x);
+        let end_col = min(end_col, clip.x + clip.width);
+        let mask = mask & clip.band_mask(row);
+        if start_col >= end_col || mask == 0 {
+            return;
+        }
+
+        let offset = row * self.width;
+        let mut changed: Option<(usize, usize)> = None;
+        for col in start_col..end_col {
+            let byte = &mut self.buffer[offset + col];
+            let new = (*byte & !mask) | (update(col, *byte) & mask);
+            if new != *byte {
+                *byte = new;
+                changed = Some((changed.map_or(col, |(start, _)| start), col + 1));
+            }
+        }
+        if let Some((start, end)) = changed {
+            self.mark_dirty(row, start, end);
+        }
+    }
+
+    /// Determine if a pixel is on (without bounds checking)
+    fn is_on(&self, x: usize, y: usize) -> bool {
+        (self.buffer[(self.width * (y >> 3)) + x] >> (y & 7)) & 0x01 != 0
+    }
+
+    /// Pixels packed 8 to a byte in horizontal rows (most significant bit on
+    /// the left) with set bits for pixels that are on, as used by image formats
+    fn packed_rows(&self) -> Vec<u8> {
+        let stride = self.width.div_ceil(8);
+        let mut rows = vec![0u8; stride * self.height];
+        for y in 0..self.height {
+            for x in 0..self.width {
+                if self.is_on(x, y) {
+                    rows[y * stride + (x >> 3)] |= 0x80 >> (x & 7);
+                }
+            }
+        }
+        rows
+    }
+
+    ///
+    /// Export as a binary PBM image, pixels that are on are black
+    ///
+    /// The image can be loaded back with `Image::decode` for comparing against
+    /// golden images.
+    ///
+    pub fn to_pbm(&self) -> Vec<u8> {
+        let mut pbm = format!("P4\n{} {}\n", self.width, self.height).into_bytes();
+        pbm.extend(self.packed_rows());
+        pbm
+    }
+
+    ///
+    /// Export as a 1 bit greyscale PNG image, pixels that are on are black
+    ///
+    #[cfg(feature = "png")]
+    pub fn to_png(&self) -> Result<Vec<u8>, Error> {
+        let rows: Vec<u8> = self.packed_rows().iter().map(|byte| !byte).collect();
+        let mut png = Vec::new();
+        let mut encoder = png::Encoder::new(&mut png, self.width as u32, self.height as u32);
+        encoder.set_color(png::ColorType::Grayscale);
+        encoder.set_depth(png::BitDepth::One);
+        let mut writer = encoder.write_header()?;
+        writer.write_image_data(&rows)?;
+        writer.finish()?;
+        Ok(png)
+    }
+
+    ///
+    /// Dump as ASCII art, one line per row of pixels with `#` for pixels that
+    /// are on and `.` for pixels that are off
+    ///
+    pub fn to_ascii(&self) -> String {
+        let mut ascii = String::with_capacity((self.width + 1) * self.height);
+        for y in 0..self.height {
+            ascii.extend((0..self.width).map(|x| if self.is_on(x, y) { '#' } else { '.' }));
+            ascii.push('\n');
+        }
+        ascii
+    }
+
+    ///
+    /// Save to a file, the format is chosen by the extension (`.pbm`, `.png` or
+    /// `.txt` for ASCII art)
+    ///
+    #[cfg(feature = "std")]
+    pub fn save<P: AsRef<std::path::Path>>(&self, path: P) -> Result<(), Error> {
+        let path = path.as_ref();
+        let data = match path.extension().and_then(|ext| ext.to_str()) {
+            Some("pbm") => self.to_pbm(),
+            #[cfg(feature = "png")]
+            Some("png") => self.to_png()?,
+            Some("txt") => self.to_ascii().into_bytes(),
+            ext => {
+                return Err(Error::InvalidImage(format!(
+                    "unsupported image format {:?}",
+                    ext.unwrap_or_default()
+                )))
+            }
+        };
+        std::fs::write(path, data)?;
+        Ok(())
+    }
+
+    /// Print a character with any columns from `end_col` onwards clipped
+    fn print_glyph(
+        &mut self,
+        c: char,
+        row: usize,
+        col: usize,
+        end_col: usize,
+        font: &dyn Font,
+        colour: Pixel,
+    ) -> usize {
+        let glyph = match font.glyph_or_replacement(c) {
+            Some(glyph) => glyph,
+            None => return 0,
+        };
+        let end_col = min(end_col, self.width);
+        if row >= self.dirty.len() || col >= end_col {
+            return glyph.width;
+        }
+
+        // Align the bottom of the font with the bottom of the last row, leaving
+        // a line of spacing below (that glyphs may extend into)
+        let rows = min(text_rows(font), self.dirty.len() - row);
+        let top = (text_rows(font) * 8) as i32 - 1 - font.height() as i32 + glyph.top;
+        let width = min(glyph.width, end_col - col);
+        for band in 0..rows {
+            let offset = top - (band * 8) as i32;
+            self.update_band(row + band, col, col + width, 0xFF, |x, _| {
+                let slice = (0..glyph.height)
+                    .filter(|&y| (0..8).contains(&(y as i32 + offset)) && glyph.pixel(x - col, y))
+                    .fold(0u8, |slice, y| slice | (1 << (y as i32 + offset)));
+                match colour {
+                    Pixel::On => slice,
+                    Pixel::Off => !slice,
+                }
+            });
         }
+        glyph.width
+    }
+}
+
+///
+/// Canvases are equal if they have the same size and pixels, the dirty state is
+/// ignored (e.g. to compare against a golden image)
+///
+impl PartialEq for MonochromeCanvas {
+    fn eq(&self, other: &Self) -> bool {
+        self.width == other.width && self.height == other.height && self.buffer == other.buffer
+    }
+}
+
+impl core::fmt::Debug for MonochromeCanvas {
+    fn fmt(&self, fmt: &mut core::fmt::Formatter) -> core::fmt::Result {
+        writeln!(fmt, "MonochromeCanvas {}x{}", self.width, self.height)?;
+        fmt.write_str(&self.to_ascii())
     }
 }
 
@@ -167,83 +576,48 @@ impl Canvas<Pixel> for MonochromeCanvas {
         self.height
     }
 
-    fn data_size(&self) -> usize {
-        self.buffer.len()
-    }
-
-    fn data(&self) -> &[u8] {
-        self.buffer.as_slice()
-    }
-
     fn is_dirty(&self) -> bool {
-        self.dirty
+        self.dirty.iter().any(|band| band.is_some())
     }
 
     fn clear_dirty_flag(&mut self) {
-        self.dirty = false;
+        for band in self.dirty.iter_mut() {
+            *band = None;
+        }
     }
 
     fn invert(&mut self) {
-        for byte in self.buffer.iter_mut() {
-            *byte = !(*byte);
+        for row in 0..self.dirty.len() {
+            self.invert_row(row);
         }
-        self.dirty = true;
     }
 
     ///
     /// Invert a row (8 pixels)
     ///
     fn invert_row(&mut self, row: usize) {
-        let start = row * self.width;
-        let end = start + self.width;
-        for byte in self.buffer[start..end].iter_mut() {
-            *byte = !*byte;
-        }
+        self.invert_row_slice(row, 0, self.width);
     }
 
     ///
     /// Invert part of a row (8 pixels)
     ///
     fn invert_row_slice(&mut self, row: usize, start_col: usize, end_col: usize) {
-        let start = row * self.width + start_col;
-        let end = start + (end_col - start_col);
-        for byte in self.buffer[start..end].iter_mut() {
-            *byte = !*byte;
-        }
+        self.update_band(row, start_col, end_col, 0xFF, |_, byte| !byte);
     }
 
     ///
     /// Fill the entire display with a Pixel
     ///
     fn fill(&mut self, colour: Pixel) {
-        let value = match colour {
-            Pixel::On => 0xFFu8,
-            Pixel::Off => 0x00u8,
-        };
-
-        for byte in self.buffer.iter_mut() {
-            *byte = value;
-        }
-
-        self.dirty = true;
+        self.fill_rows(0, self.dirty.len(), colour);
     }
 
     ///
     /// Fill the entire canvas with a single colour
     ///
     fn fill_row(&mut self, row: usize, colour: Pixel) {
-        let value = match colour {
-            Pixel::On => 0xFFu8,
-            Pixel::Off => 0x00u8,
-        };
-
-        let start = row * self.width;
-        let end = start + self.width;
-        for byte in self.buffer[start..end].iter_mut() {
-            *byte = value;
-        }
-
-        self.dirty = true;
+        self.fill_rows(row, row + 1, colour);
     }
 
     ///
@@ -255,13 +629,9 @@ impl Canvas<Pixel> for MonochromeCanvas {
             Pixel::Off => 0x00u8,
         };
 
-        let start = start_row * self.width;
-        let end = end_row * self.width;
-        for byte in self.buffer[start..end].iter_mut() {
-            *byte = value;
+        for row in start_row..end_row {
+            self.update_band(row, 0, self.width, 0xFF, |_, _| value);
         }
-
-        self.dirty = true;
     }
 
     ///
@@ -270,83 +640,445 @@ impl Canvas<Pixel> for MonochromeCanvas {
     fn set_pixel(&mut self, x: usize, y: usize, colour: Pixel) {
         let width = self.width();
         let height = self.height();
-        if (x > width) | (y > height) {
+        if (x >= width) | (y >= height) {
             return;
         }
 
-        let byte_index = (width * (y >> 3)) + x;
-        match colour {
-            Pixel::On => self.buffer[byte_index] |= 1 << (y & 7),
-            Pixel::Off => self.buffer[byte_index] &= !(1 << (y & 7)),
-        }
-
-        self.dirty = true;
+        let value = match colour {
+            Pixel::On => 0xFFu8,
+            Pixel::Off => 0x00u8,
+        };
+        self.update_band(y >> 3, x, x + 1, 1 << (y & 7), |_, _| value);
     }
 
     ///
     /// Get state of a pixel
     ///
     fn pixel(&self, x: usize, y: usize) -> Option<Pixel> {
-        if (x > self.width) | (y > self.height) {
+        if (x >= self.width) | (y >= self.height) {
             return None;
         }
 
         let byte_index = (self.width * (y >> 3)) + x;
-        let pixel = self.buffer[byte_index] >> ((y & 7) & 0x01);
+        let pixel = (self.buffer[byte_index] >> (y & 7)) & 0x01;
         Some(if pixel == 0 { Pixel::Off } else { Pixel::On })
     }
 
     ///
-    /// Copy canvas
+    /// Horizontal line, sets the same bit across a run of bytes in a row band
+    ///
+    fn draw_hline(&mut self, x: i32, y: i32, w: i32, colour: Pixel) {
+        if y < 0 || y >= self.height as i32 {
+            return;
+        }
+        let start = x.max(0) as usize;
+        let end = (x + w).clamp(0, self.width as i32) as usize;
+        if start >= end {
+            return;
+        }
+
+        let value = match colour {
+            Pixel::On => 0xFFu8,
+            Pixel::Off => 0x00u8,
+        };
+        self.update_band((y as usize) >> 3, start, end, 1 << (y & 7), |_, _| value);
+    }
+
+    ///
+    /// Vertical line, sets a run of bits in a column one row band at a time
+    ///
+    fn draw_vline(&mut self, x: i32, y: i32, h: i32, colour: Pixel) {
+        self.fill_rect(x, y, 1, h, colour);
+    }
+
+    ///
+    /// Filled rectangle, applies a mask of the covered bits to each byte of the
+    /// row bands spanned
+    ///
+    fn fill_rect(&mut self, x: i32, y: i32, w: i32, h: i32, colour: Pixel) {
+        let x_start = x.max(0) as usize;
+        let x_end = (x + w).clamp(0, self.width as i32) as usize;
+        let y_start = y.max(0) as usize;
+        let y_end = (y + h).clamp(0, self.height as i32) as usize;
+        if x_start >= x_end || y_start >= y_end {
+            return;
+        }
+
+        let value = match colour {
+            Pixel::On => 0xFFu8,
+            Pixel::Off => 0x00u8,
+        };
+        let rect = Rect::new(x_start, y_start, x_end - x_start, y_end - y_start);
+        for row in (y_start >> 3)..=((y_end - 1) >> 3) {
+            self.update_band(row, x_start, x_end, rect.band_mask(row), |_, _| value);
+        }
+    }
+
+    ///
+    /// Copy the top left of a canvas a band at a time, clipped to the smaller
+    /// of the two canvases
     ///
     fn copy_from(&mut self, canvas: &dyn Canvas<Pixel>) {
-        self.buffer = canvas.data().to_vec();
+        let width = min(self.width, canvas.width());
+        let height = min(self.height, canvas.height());
+        let rect = Rect::new(0, 0, width, height);
+        for row in 0..height.div_ceil(8) {
+            self.update_band(row, 0, width, rect.band_mask(row), |col, _| {
+                (0..8)
+                    .filter(|bit| matches!(canvas.pixel(col, row * 8 + bit), Some(Pixel::On)))
+                    .fold(0u8, |byte, bit| byte | (1 << bit))
+            });
+        }
     }
 
     ///
-    /// Print single character
+    /// Draw a bitmap, each band of the bitmap is shifted into the one or two
+    /// bands of the canvas it overlaps
     ///
-    fn print_char(&mut self, c: char, row: usize, col: usize, colour: Pixel) -> usize {
-        let raw = c as usize;
-        if raw < 0x20 || raw > 0x7F {
-            return 0;
+    fn blit(&mut self, bitmap: &Bitmap, x: i32, y: i32, mode: BlitMode) {
+        let bands = self.dirty.len() as i32;
+        let start_col = max(x, 0);
+        let end_col = min(x + bitmap.width() as i32, self.width as i32);
+        if start_col >= end_col {
+            return;
         }
-        let char_idx = raw - 0x20;
-        let (width, glyph) = FONT_NX5[char_idx];
-        for slice in 0..(width as usize) {
-            self.buffer[(row * self.width) + col + slice] = match colour {
-                Pixel::On => glyph[slice] << 2,
-                Pixel::Off => !(glyph[slice] << 2),
+
+        for band in 0..bitmap.height().div_ceil(8) {
+            let top = y + (band * 8) as i32;
+            let (row, shift) = (top.div_euclid(8), top.rem_euclid(8));
+            for (target, shift) in [(row, shift), (row + 1, shift - 8)] {
+                if target < 0 || target >= bands || shift <= -8 {
+                    continue;
+                }
+                let (start, end) = (start_col as usize, end_col as usize);
+                self.update_band(target as usize, start, end, 0xFF, |col, dest| {
+                    let (byte, mask) = bitmap.column((col as i32 - x) as usize, band, mode);
+                    let (byte, mask) = if shift >= 0 {
+                        (byte << shift, mask << shift)
+                    } else {
+                        (byte >> -shift, mask >> -shift)
+                    };
+                    mode.apply(dest, byte, mask)
+                });
             }
         }
-        self.dirty = true;
-        width as usize
+    }
+
+    ///
+    /// Print a single line of text, clipped to `width` pixels
+    ///
+    fn print_clipped(
+        &mut self,
+        s: &str,
+        row: usize,
+        col: usize,
+        width: usize,
+        font: &dyn Font,
+        colour: Pixel,
+    ) {
+        let end = col + width;
+        let mut col = col;
+        for c in s.chars().take_while(|&c| c != '\n') {
+            if col >= end {
+                break;
+            }
+            self.print_glyph(c, row, col, end, font, colour.clone());
+            col += font.advance(c);
+        }
+    }
+
+    ///
+    /// Print single character
+    ///
+    fn print_char(
+        &mut self,
+        c: char,
+        row: usize,
+        col: usize,
+        font: &dyn Font,
+        colour: Pixel,
+    ) -> usize {
+        self.print_glyph(c, row, col, self.width, font, colour)
     }
 
     ///
     /// Vertical scroll the rows in a particular direction
     ///
     fn vscroll_rows(&mut self, row_start: usize, row_end: usize, direction: Direction) {
-        let start = min(row_start, row_end) * self.width;
-        let end = max(row_start, row_end) * self.width;
-        match direction {
-            Direction::Up => {
-                for row in (start..end).rev() {
-                    self.buffer[row + self.width] = self.buffer[row];
-                }
-                for row in start..(start + self.width) {
-                    self.buffer[row] = 0;
-                }
-            }
-            Direction::Down => {
-                for row in start..end {
-                    self.buffer[row] = self.buffer[row + self.width];
-                }
-                for row in end..(end + self.width) {
-                    self.buffer[row] = 0;
-                }
+        let (start, end) = (min(row_start, row_end), max(row_start, row_end));
+        let width = self.width;
+        // Rows are copied in an order that reads each band before it is replaced
+        let (rows, offset, cleared): (Vec<usize>, isize, usize) = match direction {
+            Direction::Up => ((start + 1..=end).rev().collect(), -1, start),
+            Direction::Down => ((start..end).collect(), 1, end),
+        };
+        for row in rows {
+            let source = (row as isize + offset) as usize * width;
+            let band = self.buffer[source..source + width].to_vec();
+            self.update_band(row, 0, width, 0xFF, |col, _| band[col]);
+        }
+        self.update_band(cleared, 0, width, 0xFF, |_, _| 0);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gui::font::{GlyphRange, TableFont, NX5, NX6};
+    use crate::gui::graphics::Graphics;
+    use crate::gui::image::{Dither, Image};
+    use crate::gui::logo::{LOGO, LOGO_HEIGHT, LOGO_WIDTH};
+
+    /// Load a PBM image exported with `to_pbm`
+    fn load(pbm: &[u8]) -> MonochromeCanvas {
+        Image::decode(pbm).unwrap().to_canvas(Dither::default())
+    }
+
+    fn drawing() -> MonochromeCanvas {
+        let mut canvas = MonochromeCanvas::new(21, 16);
+        canvas.draw_rect(0, 0, 21, 16, Pixel::On);
+        canvas.draw_line(0, 0, 20, 15, Pixel::On);
+        canvas.print("AB", 1, 2, &NX5, Pixel::On);
+        canvas
+    }
+
+    fn is_on(canvas: &dyn Canvas<Pixel>, x: usize, y: usize) -> bool {
+        matches!(canvas.pixel(x, y), Some(Pixel::On))
+    }
+
+    fn clean_canvas() -> MonochromeCanvas {
+        let mut canvas = MonochromeCanvas::new(32, 16);
+        canvas.clear_dirty_flag();
+        canvas
+    }
+
+    #[test]
+    fn new_canvas_is_dirty() {
+        let canvas = MonochromeCanvas::new(32, 16);
+        assert_eq!(canvas.dirty_band(0), Some((0, 32)));
+        assert_eq!(canvas.dirty_band(1), Some((0, 32)));
+    }
+
+    #[test]
+    fn only_changed_columns_are_dirty() {
+        let mut canvas = clean_canvas();
+        canvas.set_pixel(5, 9, Pixel::On);
+        canvas.set_pixel(3, 10, Pixel::On);
+        assert!(canvas.is_dirty());
+        assert_eq!(canvas.dirty_band(0), None);
+        assert_eq!(canvas.dirty_band(1), Some((3, 6)));
+
+        canvas.clear_dirty_flag();
+        canvas.fill_rect(0, 0, 10, 4, Pixel::On);
+        assert_eq!(canvas.dirty_band(0), Some((0, 10)));
+        assert_eq!(canvas.dirty_band(1), None);
+    }
+
+    #[test]
+    fn unchanged_writes_are_not_dirty() {
+        let mut canvas = clean_canvas();
+        canvas.fill(Pixel::Off);
+        canvas.set_pixel(4, 4, Pixel::Off);
+        canvas.draw_hline(0, 3, 32, Pixel::Off);
+        assert!(!canvas.is_dirty());
+
+        canvas.draw_hline(0, 3, 32, Pixel::On);
+        canvas.clear_dirty_flag();
+        canvas.draw_hline(0, 3, 32, Pixel::On);
+        assert!(!canvas.is_dirty());
+
+        // Partly overlapping write only marks the new pixels
+        canvas.fill_rect(20, 0, 12, 8, Pixel::On);
+        canvas.clear_dirty_flag();
+        canvas.fill_rect(16, 0, 16, 8, Pixel::On);
+        assert_eq!(canvas.dirty_band(0), Some((16, 20)));
+    }
+
+    #[test]
+    fn pbm_round_trip() {
+        let canvas = drawing();
+        let pbm = canvas.to_pbm();
+        assert!(pbm.starts_with(b"P4\n21 16\n"));
+        assert_eq!(pbm.len(), 9 + 3 * 16);
+        assert_eq!(load(&pbm), canvas);
+    }
+
+    #[cfg(feature = "png")]
+    #[test]
+    fn png_round_trip() {
+        let canvas = drawing();
+        assert_eq!(load(&canvas.to_png().unwrap()), canvas);
+    }
+
+    #[test]
+    fn ascii_art() {
+        let mut canvas = MonochromeCanvas::new(3, 8);
+        canvas.set_pixel(1, 0, Pixel::On);
+        canvas.set_pixel(2, 7, Pixel::On);
+        assert_eq!(
+            canvas.to_ascii(),
+            ".#.\n...\n...\n...\n...\n...\n...\n..#\n"
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn save_by_extension() {
+        let dir = std::env::temp_dir().join(std::format!("mm-save-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let canvas = drawing();
+
+        canvas.save(dir.join("canvas.pbm")).unwrap();
+        assert_eq!(
+            load(&std::fs::read(dir.join("canvas.pbm")).unwrap()),
+            canvas
+        );
+        canvas.save(dir.join("canvas.txt")).unwrap();
+        assert_eq!(
+            std::fs::read_to_string(dir.join("canvas.txt")).unwrap(),
+            canvas.to_ascii()
+        );
+        assert!(matches!(
+            canvas.save(dir.join("canvas.gif")),
+            Err(Error::InvalidImage(_))
+        ));
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn logo_matches_golden_image() {
+        let logo = MonochromeCanvas::from_buffer(LOGO_WIDTH, LOGO_HEIGHT, &LOGO);
+        let golden = include_bytes!("../../tests/golden/logo.pbm");
+        assert_eq!(logo.to_pbm(), golden);
+        assert_eq!(load(golden), logo);
+    }
+
+    #[test]
+    fn text_matches_golden_image() {
+        let mut canvas = MonochromeCanvas::new(64, 16);
+        canvas.print("Hello \u{266A}", 0, 0, &NX6, Pixel::On);
+        canvas.print(
+            "\u{C0}\u{C9}\u{CE} \u{D5}\u{DF}\u{2026}",
+            1,
+            0,
+            &NX5,
+            Pixel::On,
+        );
+        assert_eq!(canvas, load(include_bytes!("../../tests/golden/text.pbm")));
+    }
+
+    /// 12 pixel high bar with a notch in the second band of the right column
+    static TALL_GLYPHS: [(u8, &[u8]); 1] = [(2, &[0xFF, 0xFF, 0x0F, 0x0D])];
+    static TALL_RANGES: [GlyphRange; 1] = [GlyphRange::new('I', 0, 0, &TALL_GLYPHS)];
+    static TALL_REPLACEMENT: (u8, &[u8]) = (1, &[0xFF, 0x0F]);
+
+    #[test]
+    fn print_fonts_taller_than_a_row() {
+        let font = TableFont::new(&TALL_RANGES, &TALL_REPLACEMENT, 12, 12, 16);
+        assert_eq!(text_rows(&font), 2);
+        assert_eq!(text_rows(&NX6), 1);
+        let glyph = font.glyph('I').unwrap();
+
+        // The bottom of the font is one pixel above the bottom of the second row
+        let mut canvas = MonochromeCanvas::new(8, 40);
+        canvas.print("I\nI", 0, 1, &font, Pixel::On);
+        for y in 0..40 {
+            for x in 0..8 {
+                let expected = match (x, y) {
+                    (1..=2, 3..=14) => glyph.pixel(x - 1, y - 3),
+                    (0..=1, 19..=30) => glyph.pixel(x, y - 19),
+                    _ => false,
+                };
+                assert_eq!(is_on(&canvas, x, y), expected, "({}, {})", x, y);
             }
         }
-        self.dirty = true;
+        assert!(!is_on(&canvas, 2, 12));
+        assert!(is_on(&canvas, 2, 14));
+
+        // Rows past the bottom of the canvas are clipped
+        canvas.print("I", 4, 4, &font, Pixel::On);
+        assert!((35..40).all(|y| is_on(&canvas, 4, y)));
+    }
+
+    #[test]
+    fn clip_stack() {
+        let mut canvas = clean_canvas();
+        canvas.push_clip(Rect::new(4, 4, 8, 8));
+        canvas.push_clip(Rect::new(8, 0, 16, 16));
+        assert_eq!(canvas.clip(), Rect::new(8, 4, 4, 8));
+
+        canvas.fill(Pixel::On);
+        assert!(is_on(&canvas, 8, 4));
+        assert!(is_on(&canvas, 11, 11));
+        assert!(!is_on(&canvas, 7, 4));
+        assert!(!is_on(&canvas, 8, 3));
+        assert!(!is_on(&canvas, 12, 12));
+        assert_eq!(canvas.dirty_band(0), Some((8, 12)));
+
+        canvas.pop_clip();
+        assert_eq!(canvas.clip(), Rect::new(4, 4, 8, 8));
+        canvas.pop_clip();
+        assert_eq!(canvas.clip(), Rect::new(0, 0, 32, 16));
+        canvas.pop_clip();
+        assert_eq!(canvas.clip(), Rect::new(0, 0, 32, 16));
+    }
+
+    #[test]
+    fn viewport_is_clipped_and_restores_the_clip() {
+        let mut canvas = clean_canvas();
+        {
+            let mut viewport = canvas.viewport(1, 4, 1, 8);
+            assert_eq!((viewport.width(), viewport.height()), (8, 8));
+            viewport.fill_rect(-2, -2, 20, 20, Pixel::On);
+            viewport.set_pixel(9, 0, Pixel::On);
+            assert!(is_on(&viewport, 0, 0));
+            assert!(viewport.pixel(8, 0).is_none());
+
+            let mut inner = viewport.viewport(0, 6, 1, 8);
+            inner.fill(Pixel::Off);
+        }
+        assert_eq!(canvas.clip(), Rect::new(0, 0, 32, 16));
+        assert!(is_on(&canvas, 4, 8));
+        assert!(is_on(&canvas, 9, 15));
+        assert!(!is_on(&canvas, 10, 8));
+        assert!(!is_on(&canvas, 3, 8));
+        assert!(!is_on(&canvas, 4, 7));
+        assert!(!is_on(&canvas, 13, 8));
+    }
+
+    #[test]
+    fn copy_from_a_smaller_canvas() {
+        let mut source = MonochromeCanvas::new(8, 8);
+        source.fill(Pixel::On);
+        let mut canvas = clean_canvas();
+        canvas.copy_from(&source);
+        assert!(is_on(&canvas, 7, 7));
+        assert!(!is_on(&canvas, 8, 0));
+        assert!(!is_on(&canvas, 0, 8));
+        assert_eq!(canvas.dirty_band(0), Some((0, 8)));
+        assert_eq!(canvas.dirty_band(1), None);
+
+        // Larger source is clipped to the canvas
+        let mut small = MonochromeCanvas::new(8, 8);
+        small.copy_from(&drawing());
+        assert!(is_on(&small, 0, 0));
+        assert!(!is_on(&small, 7, 1));
+    }
+
+    #[test]
+    fn copy_from_a_viewport() {
+        let mut source = clean_canvas();
+        source.set_pixel(21, 9, Pixel::On);
+        let mut canvas = MonochromeCanvas::new(8, 8);
+        canvas.copy_from(&source.viewport(1, 20, 1, 8));
+        assert!(is_on(&canvas, 1, 1));
+        assert_eq!(
+            (0..8)
+                .flat_map(|y| (0..8).map(move |x| (x, y)))
+                .filter(|&(x, y)| is_on(&canvas, x, y))
+                .count(),
+            1
+        );
     }
 }
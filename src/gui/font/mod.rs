@@ -1,7 +1,278 @@
+// Only the ASCII `FONT` tables are generated (by `tools/font-gen` from
+// `tools/nx5-font.bmp` and `tools/nx6-font.bmp`), the Latin-1, symbol and
+// replacement glyphs have no source bitmaps and are drawn by hand in the same layout
 mod nx5;
 mod nx6;
 
-#[allow(unused)]
-pub use nx5::FONT as FONT_NX5;
-#[allow(unused)]
-pub use nx6::FONT as FONT_NX6;
+/// Glyph ranges shared by the NX fonts, accents on Latin-1 use the rows above
+macro_rules! nx_ranges {
+    ($font:ident, $above:expr $(, $extra:expr)*) => {
+        [
+            GlyphRange::new(' ', 0, 0, &$font::FONT),
+            GlyphRange::new('\u{A0}', $above, 1, &$font::LATIN1),
+            $($extra,)*
+            GlyphRange::new('\u{2026}', 0, 0, &$font::ELLIPSIS),
+            GlyphRange::new('\u{2190}', 0, 0, &$font::ARROWS),
+            GlyphRange::new('\u{25A0}', 0, 0, &$font::SHAPES),
+            GlyphRange::new('\u{266A}', 0, 0, &$font::MUSIC),
+            GlyphRange::new('\u{2713}', 0, 0, &$font::DINGBATS),
+        ]
+    };
+}
+
+const NX5_RANGES: [GlyphRange; 8] =
+    nx_ranges!(nx5, 2, GlyphRange::new('\u{F7}', 2, 1, &nx5::LATIN1_LOWER));
+const NX6_RANGES: [GlyphRange; 7] = nx_ranges!(nx6, 1);
+
+///
+/// 5 pixel high proportional font (upper case only), compact for dense lists
+///
+pub const NX5: TableFont =
+    TableFont::new(&NX5_RANGES, &nx5::REPLACEMENT, 5, 5, 8).with_map(latin1_upper_case);
+
+/// Lower case Latin-1 letters are drawn with the upper case glyphs of NX5
+fn latin1_upper_case(c: char) -> char {
+    match c {
+        '\u{E0}'..='\u{FE}' if c != '\u{F7}' => char::from_u32(c as u32 - 0x20).unwrap_or(c),
+        _ => c,
+    }
+}
+
+///
+/// 6 pixel high proportional font, suited to headings
+///
+pub const NX6: TableFont = TableFont::new(&NX6_RANGES, &nx6::REPLACEMENT, 6, 6, 8);
+
+///
+/// Bitmap of a single character
+///
+/// Data uses the same layout as the display, each byte is a column of 8 pixels
+/// (least significant bit at the top) with a band of `width` bytes for each 8
+/// pixels of height. The top of a glyph is relative to the top of the font,
+/// glyphs with accents start above the font (`top` is negative).
+///
+#[derive(Copy, Clone, Debug)]
+pub struct Glyph<'a> {
+    pub width: usize,
+    pub height: usize,
+    pub top: i32,
+    pub data: &'a [u8],
+}
+
+impl Glyph<'_> {
+    ///
+    /// Determine if a pixel of the glyph is set
+    ///
+    pub fn pixel(&self, x: usize, y: usize) -> bool {
+        if x >= self.width || y >= self.height {
+            return false;
+        }
+        self.data
+            .get((y >> 3) * self.width + x)
+            .is_some_and(|byte| (byte >> (y & 7)) & 0x01 != 0)
+    }
+}
+
+///
+/// Font used to render text
+///
+pub trait Font {
+    ///
+    /// Glyph for a character, `None` if the font does not contain the character
+    ///
+    fn glyph(&self, c: char) -> Option<Glyph<'_>>;
+
+    ///
+    /// Height of the glyphs in pixels
+    ///
+    fn height(&self) -> usize;
+
+    ///
+    /// Distance from the top of a glyph to the baseline
+    ///
+    fn baseline(&self) -> usize;
+
+    ///
+    /// Distance between the top of each line of text
+    ///
+    fn line_spacing(&self) -> usize;
+
+    ///
+    /// Glyph drawn in place of characters missing from the font
+    ///
+    fn replacement(&self) -> Option<Glyph<'_>> {
+        None
+    }
+
+    ///
+    /// Glyph to draw for a character, the replacement glyph is used for
+    /// printable characters the font does not contain
+    ///
+    fn glyph_or_replacement(&self, c: char) -> Option<Glyph<'_>> {
+        match self.glyph(c) {
+            Some(glyph) => Some(glyph),
+            None if c.is_control() => None,
+            None => self.replacement(),
+        }
+    }
+
+    ///
+    /// Horizontal space taken by a character including spacing
+    ///
+    fn advance(&self, c: char) -> usize {
+        self.glyph_or_replacement(c)
+            .map_or(0, |glyph| glyph.width + 1)
+    }
+}
+
+///
+/// Consecutive characters of a `TableFont`
+///
+/// Glyphs can extend `above` the top and `below` the bottom of the font by a
+/// number of rows (e.g. for accents and cedillas). Each entry is the width of
+/// the glyph followed by its data (see `Glyph`), `width` bytes for each band of
+/// 8 rows. Entries with a width of 0 are missing from the font.
+///
+pub struct GlyphRange {
+    first: char,
+    above: usize,
+    below: usize,
+    glyphs: &'static [(u8, &'static [u8])],
+}
+
+impl GlyphRange {
+    pub const fn new(
+        first: char,
+        above: usize,
+        below: usize,
+        glyphs: &'static [(u8, &'static [u8])],
+    ) -> Self {
+        GlyphRange {
+            first,
+            above,
+            below,
+            glyphs,
+        }
+    }
+
+    /// Entry for a character if it is within the range
+    fn entry(&self, c: char) -> Option<&'static (u8, &'static [u8])> {
+        let idx = (c as u32).checked_sub(self.first as u32)? as usize;
+        self.glyphs.get(idx)
+    }
+}
+
+///
+/// Font built from lookup tables of glyphs (in the layout written by `tools/font-gen`)
+///
+/// The tables of glyphs cover sparse ranges of Unicode code points (see
+/// `GlyphRange`), the replacement glyph is the height of the font. Characters can be mapped before
+/// they are looked up (see `with_map`) so that glyphs are shared rather than
+/// duplicated in the tables.
+///
+pub struct TableFont {
+    ranges: &'static [GlyphRange],
+    replacement: &'static (u8, &'static [u8]),
+    height: usize,
+    baseline: usize,
+    line_spacing: usize,
+    map: fn(char) -> char,
+}
+
+impl TableFont {
+    pub const fn new(
+        ranges: &'static [GlyphRange],
+        replacement: &'static (u8, &'static [u8]),
+        height: usize,
+        baseline: usize,
+        line_spacing: usize,
+    ) -> Self {
+        TableFont {
+            ranges,
+            replacement,
+            height,
+            baseline,
+            line_spacing,
+            map: unmapped,
+        }
+    }
+
+    ///
+    /// Map characters before looking up their glyph, eg to draw lower case
+    /// with the upper case glyphs
+    ///
+    pub const fn with_map(self, map: fn(char) -> char) -> Self {
+        TableFont { map, ..self }
+    }
+}
+
+fn unmapped(c: char) -> char {
+    c
+}
+
+impl Font for TableFont {
+    fn glyph(&self, c: char) -> Option<Glyph<'_>> {
+        let c = (self.map)(c);
+        self.ranges.iter().find_map(|range| {
+            let (width, data) = range.entry(c)?;
+            if *width == 0 {
+                return None;
+            }
+            Some(Glyph {
+                width: *width as usize,
+                height: range.above + self.height + range.below,
+                top: -(range.above as i32),
+                data,
+            })
+        })
+    }
+
+    fn replacement(&self) -> Option<Glyph<'_>> {
+        let (width, data) = self.replacement;
+        Some(Glyph {
+            width: *width as usize,
+            height: self.height,
+            top: 0,
+            data,
+        })
+    }
+
+    fn height(&self) -> usize {
+        self.height
+    }
+
+    fn baseline(&self) -> usize {
+        self.baseline
+    }
+
+    fn line_spacing(&self) -> usize {
+        self.line_spacing
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn columns(font: &TableFont, c: char) -> Option<&[u8]> {
+        font.glyph(c).map(|glyph| glyph.data)
+    }
+
+    #[test]
+    fn nx5_lower_case_latin1_uses_upper_case() {
+        assert_eq!(columns(&NX5, 'é'), columns(&NX5, 'É'));
+        assert_eq!(columns(&NX5, 'þ'), columns(&NX5, 'Þ'));
+        assert_eq!(NX5.glyph('é').map(|glyph| glyph.top), Some(-2));
+        assert_ne!(columns(&NX5, '÷'), columns(&NX5, '×'));
+        assert!(NX5.glyph('ÿ').is_some());
+        assert!(NX5.glyph('ß').is_some());
+    }
+
+    #[test]
+    fn missing_glyphs_use_the_replacement() {
+        assert!(NX6.glyph('\u{100}').is_none());
+        assert!(NX6.glyph_or_replacement('\u{100}').is_some());
+        assert!(NX6.glyph_or_replacement('\n').is_none());
+        assert_ne!(columns(&NX6, 'é'), columns(&NX6, 'É'));
+    }
+}
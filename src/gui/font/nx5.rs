@@ -1,98 +1,255 @@
-pub const FONT: [(u8, [u8; 5]); 96] = [
-    (3, [0, 0, 0, 0, 0]),      //
-    (1, [23, 0, 0, 0, 0]),     // !
-    (3, [3, 0, 3, 0, 0]),      // "
-    (5, [10, 31, 10, 31, 10]), // #
-    (5, [2, 21, 31, 21, 8]),   // $
-    (5, [17, 8, 4, 2, 17]),    // %
-    (5, [10, 21, 21, 8, 20]),  // &
-    (1, [3, 0, 0, 0, 0]),      // '
-    (2, [14, 17, 0, 0, 0]),    // (
-    (2, [17, 14, 0, 0, 0]),    // )
-    (5, [10, 4, 31, 4, 10]),   // *
-    (3, [4, 14, 4, 0, 0]),     // +
-    (2, [16, 8, 0, 0, 0]),     // ,
-    (3, [4, 4, 4, 0, 0]),      // -
-    (1, [16, 0, 0, 0, 0]),     // .
-    (5, [16, 8, 4, 2, 1]),     // /
-    (5, [14, 17, 21, 17, 14]), // 0
-    (3, [18, 31, 16, 0, 0]),   // 1
-    (5, [18, 17, 25, 21, 18]), // 2
-    (5, [10, 17, 21, 21, 10]), // 3
-    (5, [12, 10, 9, 31, 8]),   // 4
-    (5, [23, 21, 21, 21, 8]),  // 5
-    (5, [14, 21, 21, 21, 8]),  // 6
-    (4, [17, 9, 5, 3, 0]),     // 7
-    (5, [10, 21, 21, 21, 10]), // 8
-    (5, [2, 21, 21, 21, 14]),  // 9
-    (1, [10, 0, 0, 0, 0]),     // :
-    (2, [16, 10, 0, 0, 0]),    // ;
-    (3, [4, 10, 17, 0, 0]),    // <
-    (3, [10, 10, 10, 0, 0]),   // =
-    (3, [17, 10, 4, 0, 0]),    // >
-    (5, [2, 1, 21, 5, 2]),     // ?
-    (4, [14, 17, 21, 22, 0]),  // @
-    (4, [31, 5, 5, 31, 0]),    // A
-    (4, [31, 21, 21, 10, 0]),  // B
-    (4, [14, 17, 17, 10, 0]),  // C
-    (4, [31, 17, 17, 14, 0]),  // D
-    (4, [31, 21, 21, 17, 0]),  // E
-    (4, [31, 5, 5, 1, 0]),     // F
-    (4, [14, 17, 21, 13, 0]),  // G
-    (4, [31, 4, 4, 31, 0]),    // H
-    (3, [17, 31, 17, 0, 0]),   // I
-    (4, [9, 17, 31, 1, 0]),    // J
-    (4, [31, 4, 10, 17, 0]),   // K
-    (3, [31, 16, 16, 0, 0]),   // L
-    (5, [31, 2, 12, 2, 31]),   // M
-    (5, [31, 2, 4, 8, 31]),    // N
-    (4, [14, 17, 17, 14, 0]),  // O
-    (4, [31, 5, 5, 2, 0]),     // P
-    (5, [14, 17, 21, 25, 14]), // Q
-    (5, [31, 5, 5, 13, 18]),   // R
-    (4, [18, 21, 21, 9, 0]),   // S
-    (5, [1, 1, 31, 1, 1]),     // T
-    (4, [15, 16, 16, 15, 0]),  // U
-    (5, [3, 12, 16, 12, 3]),   // V
-    (5, [15, 16, 12, 16, 15]), // W
-    (5, [17, 10, 4, 10, 17]),  // X
-    (5, [1, 2, 28, 2, 1]),     // Y
-    (5, [17, 25, 21, 19, 17]), // Z
-    (2, [31, 17, 0, 0, 0]),    // [
-    (5, [1, 2, 4, 8, 16]),     // \
-    (2, [17, 31, 0, 0, 0]),    // ]
-    (3, [2, 1, 2, 0, 0]),      // ^
-    (4, [16, 16, 16, 16, 0]),  // _
-    (2, [1, 2, 0, 0, 0]),      // `
-    (4, [31, 5, 5, 31, 0]),    // a
-    (4, [31, 21, 21, 10, 0]),  // b
-    (4, [14, 17, 17, 10, 0]),  // c
-    (4, [31, 17, 17, 14, 0]),  // d
-    (4, [31, 21, 21, 17, 0]),  // e
-    (4, [31, 5, 5, 1, 0]),     // f
-    (4, [14, 17, 21, 13, 0]),  // g
-    (4, [31, 4, 4, 31, 0]),    // h
-    (3, [17, 31, 17, 0, 0]),   // i
-    (4, [9, 17, 31, 1, 0]),    // j
-    (4, [31, 4, 10, 17, 0]),   // k
-    (3, [31, 16, 16, 0, 0]),   // l
-    (5, [31, 2, 12, 2, 31]),   // m
-    (5, [31, 2, 4, 8, 31]),    // n
-    (4, [14, 17, 17, 14, 0]),  // o
-    (4, [31, 5, 5, 2, 0]),     // p
-    (5, [14, 17, 21, 25, 14]), // q
-    (5, [31, 5, 5, 13, 18]),   // r
-    (4, [18, 21, 21, 9, 0]),   // s
-    (5, [1, 1, 31, 1, 1]),     // t
-    (4, [15, 16, 16, 15, 0]),  // u
-    (5, [3, 12, 16, 12, 3]),   // v
-    (5, [15, 16, 12, 16, 15]), // w
-    (5, [17, 10, 4, 10, 17]),  // x
-    (5, [1, 2, 28, 2, 1]),     // y
-    (5, [17, 25, 21, 19, 17]), // z
-    (3, [10, 21, 17, 0, 0]),   // {
-    (1, [31, 0, 0, 0, 0]),     // |
-    (3, [17, 21, 10, 0, 0]),   // }
-    (4, [2, 1, 2, 1, 0]),      // ~
-    (0, [0, 0, 0, 0, 0]),      // 
+pub const FONT: [(u8, &[u8]); 96] = [
+    (3, &[0, 0, 0]),            //
+    (1, &[23]),                 // !
+    (3, &[3, 0, 3]),            // "
+    (5, &[10, 31, 10, 31, 10]), // #
+    (5, &[2, 21, 31, 21, 8]),   // $
+    (5, &[17, 8, 4, 2, 17]),    // %
+    (5, &[10, 21, 21, 8, 20]),  // &
+    (1, &[3]),                  // '
+    (2, &[14, 17]),             // (
+    (2, &[17, 14]),             // )
+    (5, &[10, 4, 31, 4, 10]),   // *
+    (3, &[4, 14, 4]),           // +
+    (2, &[16, 8]),              // ,
+    (3, &[4, 4, 4]),            // -
+    (1, &[16]),                 // .
+    (5, &[16, 8, 4, 2, 1]),     // /
+    (5, &[14, 17, 21, 17, 14]), // 0
+    (3, &[18, 31, 16]),         // 1
+    (5, &[18, 17, 25, 21, 18]), // 2
+    (5, &[10, 17, 21, 21, 10]), // 3
+    (5, &[12, 10, 9, 31, 8]),   // 4
+    (5, &[23, 21, 21, 21, 8]),  // 5
+    (5, &[14, 21, 21, 21, 8]),  // 6
+    (4, &[17, 9, 5, 3]),        // 7
+    (5, &[10, 21, 21, 21, 10]), // 8
+    (5, &[2, 21, 21, 21, 14]),  // 9
+    (1, &[10]),                 // :
+    (2, &[16, 10]),             // ;
+    (3, &[4, 10, 17]),          // <
+    (3, &[10, 10, 10]),         // =
+    (3, &[17, 10, 4]),          // >
+    (5, &[2, 1, 21, 5, 2]),     // ?
+    (4, &[14, 17, 21, 22]),     // @
+    (4, &[31, 5, 5, 31]),       // A
+    (4, &[31, 21, 21, 10]),     // B
+    (4, &[14, 17, 17, 10]),     // C
+    (4, &[31, 17, 17, 14]),     // D
+    (4, &[31, 21, 21, 17]),     // E
+    (4, &[31, 5, 5, 1]),        // F
+    (4, &[14, 17, 21, 13]),     // G
+    (4, &[31, 4, 4, 31]),       // H
+    (3, &[17, 31, 17]),         // I
+    (4, &[9, 17, 31, 1]),       // J
+    (4, &[31, 4, 10, 17]),      // K
+    (3, &[31, 16, 16]),         // L
+    (5, &[31, 2, 12, 2, 31]),   // M
+    (5, &[31, 2, 4, 8, 31]),    // N
+    (4, &[14, 17, 17, 14]),     // O
+    (4, &[31, 5, 5, 2]),        // P
+    (5, &[14, 17, 21, 25, 14]), // Q
+    (5, &[31, 5, 5, 13, 18]),   // R
+    (4, &[18, 21, 21, 9]),      // S
+    (5, &[1, 1, 31, 1, 1]),     // T
+    (4, &[15, 16, 16, 15]),     // U
+    (5, &[3, 12, 16, 12, 3]),   // V
+    (5, &[15, 16, 12, 16, 15]), // W
+    (5, &[17, 10, 4, 10, 17]),  // X
+    (5, &[1, 2, 28, 2, 1]),     // Y
+    (5, &[17, 25, 21, 19, 17]), // Z
+    (2, &[31, 17]),             // [
+    (5, &[1, 2, 4, 8, 16]),     // \
+    (2, &[17, 31]),             // ]
+    (3, &[2, 1, 2]),            // ^
+    (4, &[16, 16, 16, 16]),     // _
+    (2, &[1, 2]),               // `
+    (4, &[31, 5, 5, 31]),       // a
+    (4, &[31, 21, 21, 10]),     // b
+    (4, &[14, 17, 17, 10]),     // c
+    (4, &[31, 17, 17, 14]),     // d
+    (4, &[31, 21, 21, 17]),     // e
+    (4, &[31, 5, 5, 1]),        // f
+    (4, &[14, 17, 21, 13]),     // g
+    (4, &[31, 4, 4, 31]),       // h
+    (3, &[17, 31, 17]),         // i
+    (4, &[9, 17, 31, 1]),       // j
+    (4, &[31, 4, 10, 17]),      // k
+    (3, &[31, 16, 16]),         // l
+    (5, &[31, 2, 12, 2, 31]),   // m
+    (5, &[31, 2, 4, 8, 31]),    // n
+    (4, &[14, 17, 17, 14]),     // o
+    (4, &[31, 5, 5, 2]),        // p
+    (5, &[14, 17, 21, 25, 14]), // q
+    (5, &[31, 5, 5, 13, 18]),   // r
+    (4, &[18, 21, 21, 9]),      // s
+    (5, &[1, 1, 31, 1, 1]),     // t
+    (4, &[15, 16, 16, 15]),     // u
+    (5, &[3, 12, 16, 12, 3]),   // v
+    (5, &[15, 16, 12, 16, 15]), // w
+    (5, &[17, 10, 4, 10, 17]),  // x
+    (5, &[1, 2, 28, 2, 1]),     // y
+    (5, &[17, 25, 21, 19, 17]), // z
+    (3, &[10, 21, 17]),         // {
+    (1, &[31]),                 // |
+    (3, &[17, 21, 10]),         // }
+    (4, &[2, 1, 2, 1]),         // ~
+    (0, &[]),                   // 
 ];
+
+pub const LATIN1: [(u8, &[u8]); 64] = [
+    (3, &[0, 0, 0]),              //
+    (1, &[116]),                  // ¡
+    (4, &[16, 40, 124, 40]),      // ¢
+    (4, &[80, 120, 84, 68]),      // £
+    (5, &[68, 56, 40, 56, 68]),   // ¤
+    (5, &[20, 24, 112, 24, 20]),  // ¥
+    (1, &[108]),                  // ¦
+    (3, &[88, 108, 52]),          // §
+    (3, &[4, 0, 4]),              // ¨
+    (5, &[56, 108, 84, 84, 56]),  // ©
+    (3, &[72, 84, 92]),           // ª
+    (4, &[16, 40, 16, 40]),       // «
+    (4, &[16, 16, 16, 48]),       // ¬
+    (3, &[16, 16, 16]),           //
+    (5, &[56, 68, 116, 92, 56]),  // ®
+    (3, &[4, 4, 4]),              // ¯
+    (3, &[8, 20, 8]),             // °
+    (3, &[72, 92, 72]),           // ±
+    (3, &[36, 52, 40]),           // ²
+    (3, &[36, 44, 20]),           // ³
+    (2, &[8, 4]),                 // ´
+    (4, &[248, 64, 32, 120]),     // µ
+    (4, &[8, 124, 4, 124]),       // ¶
+    (1, &[16]),                   // ·
+    (2, &[128, 64]),              // ¸
+    (2, &[4, 28]),                // ¹
+    (3, &[72, 84, 72]),           // º
+    (4, &[40, 16, 40, 16]),       // »
+    (5, &[44, 16, 8, 52, 96]),    // ¼
+    (5, &[44, 16, 8, 116, 80]),   // ½
+    (5, &[76, 44, 16, 40, 116]),  // ¾
+    (3, &[32, 84, 64]),           // ¿
+    (4, &[124, 21, 22, 124]),     // À
+    (4, &[124, 22, 21, 124]),     // Á
+    (4, &[126, 21, 22, 124]),     // Â
+    (4, &[126, 21, 22, 125]),     // Ã
+    (4, &[125, 20, 21, 124]),     // Ä
+    (4, &[124, 23, 23, 124]),     // Å
+    (5, &[120, 20, 124, 84, 84]), // Æ
+    (4, &[56, 196, 68, 40]),      // Ç
+    (4, &[124, 85, 86, 68]),      // È
+    (4, &[124, 86, 85, 68]),      // É
+    (4, &[126, 85, 86, 68]),      // Ê
+    (4, &[125, 84, 85, 68]),      // Ë
+    (3, &[69, 126, 68]),          // Ì
+    (3, &[70, 125, 68]),          // Í
+    (3, &[70, 125, 70]),          // Î
+    (3, &[69, 124, 69]),          // Ï
+    (5, &[84, 124, 84, 68, 56]),  // Ð
+    (5, &[126, 9, 18, 33, 124]),  // Ñ
+    (4, &[56, 69, 70, 56]),       // Ò
+    (4, &[56, 70, 69, 56]),       // Ó
+    (4, &[58, 69, 70, 56]),       // Ô
+    (4, &[58, 69, 70, 57]),       // Õ
+    (4, &[57, 68, 69, 56]),       // Ö
+    (3, &[40, 16, 40]),           // ×
+    (5, &[120, 100, 84, 76, 60]), // Ø
+    (4, &[60, 65, 66, 60]),       // Ù
+    (4, &[60, 66, 65, 60]),       // Ú
+    (4, &[62, 65, 66, 60]),       // Û
+    (4, &[61, 64, 65, 60]),       // Ü
+    (5, &[4, 10, 113, 8, 4]),     // Ý
+    (4, &[124, 40, 40, 16]),      // Þ
+    (4, &[120, 4, 84, 40]),       // ß
+];
+
+/// Lower case Latin-1 letters use the upper case glyphs, only the symbols remain
+pub const LATIN1_LOWER: [(u8, &[u8]); 9] = [
+    (3, &[16, 84, 16]),      // ÷
+    (0, &[]),                // ø
+    (0, &[]),                // ù
+    (0, &[]),                // ú
+    (0, &[]),                // û
+    (0, &[]),                // ü
+    (0, &[]),                // ý
+    (0, &[]),                // þ
+    (5, &[4, 9, 112, 9, 4]), // ÿ
+];
+
+pub const ELLIPSIS: [(u8, &[u8]); 1] = [
+    (5, &[16, 0, 16, 0, 16]), // …
+];
+
+pub const ARROWS: [(u8, &[u8]); 4] = [
+    (5, &[4, 14, 21, 4, 4]), // ←
+    (5, &[4, 2, 31, 2, 4]),  // ↑
+    (5, &[4, 4, 21, 14, 4]), // →
+    (5, &[4, 8, 31, 8, 4]),  // ↓
+];
+
+pub const SHAPES: [(u8, &[u8]); 48] = [
+    (4, &[30, 30, 30, 30]),     // ■
+    (4, &[30, 18, 18, 30]),     // □
+    (0, &[]),                   //
+    (0, &[]),                   //
+    (0, &[]),                   //
+    (0, &[]),                   //
+    (0, &[]),                   //
+    (0, &[]),                   //
+    (0, &[]),                   //
+    (0, &[]),                   //
+    (0, &[]),                   //
+    (0, &[]),                   //
+    (0, &[]),                   //
+    (0, &[]),                   //
+    (0, &[]),                   //
+    (0, &[]),                   //
+    (0, &[]),                   //
+    (0, &[]),                   //
+    (5, &[8, 12, 14, 12, 8]),   // ▲
+    (0, &[]),                   //
+    (0, &[]),                   //
+    (0, &[]),                   //
+    (3, &[31, 14, 4]),          // ▶
+    (0, &[]),                   //
+    (0, &[]),                   //
+    (0, &[]),                   //
+    (0, &[]),                   //
+    (0, &[]),                   //
+    (5, &[2, 6, 14, 6, 2]),     // ▼
+    (0, &[]),                   //
+    (0, &[]),                   //
+    (0, &[]),                   //
+    (3, &[4, 14, 31]),          // ◀
+    (0, &[]),                   //
+    (0, &[]),                   //
+    (0, &[]),                   //
+    (0, &[]),                   //
+    (0, &[]),                   //
+    (0, &[]),                   //
+    (0, &[]),                   //
+    (0, &[]),                   //
+    (0, &[]),                   //
+    (0, &[]),                   //
+    (5, &[14, 17, 17, 17, 14]), // ○
+    (0, &[]),                   //
+    (0, &[]),                   //
+    (0, &[]),                   //
+    (5, &[14, 31, 31, 31, 14]), // ●
+];
+
+pub const MUSIC: [(u8, &[u8]); 6] = [
+    (4, &[24, 24, 15, 2]),      // ♪
+    (5, &[24, 31, 1, 25, 31]),  // ♫
+    (5, &[24, 31, 3, 27, 31]),  // ♬
+    (3, &[31, 20, 8]),          // ♭
+    (3, &[15, 10, 30]),         // ♮
+    (5, &[10, 31, 10, 31, 10]), // ♯
+];
+
+pub const DINGBATS: [(u8, &[u8]); 1] = [
+    (5, &[4, 8, 4, 2, 1]), // ✓
+];
+
+pub const REPLACEMENT: (u8, &[u8]) = (5, &[31, 30, 10, 24, 31]);
@@ -1,98 +1,274 @@
-pub const FONT: [(u8, [u8; 5]); 96] = [
-    (3, [0, 0, 0, 0, 0]),      //
-    (1, [47, 0, 0, 0, 0]),     // !
-    (3, [3, 0, 3, 0, 0]),      // "
-    (5, [20, 62, 20, 62, 20]), // #
-    (5, [4, 42, 63, 42, 16]),  // $
-    (5, [34, 16, 8, 4, 34]),   // %
-    (5, [20, 42, 42, 16, 40]), // &
-    (1, [3, 0, 0, 0, 0]),      // '
-    (3, [12, 18, 33, 0, 0]),   // (
-    (3, [33, 18, 12, 0, 0]),   // )
-    (5, [20, 8, 62, 8, 20]),   // *
-    (3, [8, 28, 8, 0, 0]),     // +
-    (2, [32, 16, 0, 0, 0]),    // ,
-    (3, [8, 8, 8, 0, 0]),      // -
-    (1, [32, 0, 0, 0, 0]),     // .
-    (3, [48, 12, 3, 0, 0]),    // /
-    (5, [30, 33, 45, 33, 30]), // 0
-    (3, [34, 63, 32, 0, 0]),   // 1
-    (5, [34, 49, 41, 37, 34]), // 2
-    (5, [18, 33, 37, 37, 26]), // 3
-    (5, [24, 20, 18, 63, 16]), // 4
-    (5, [23, 37, 37, 37, 25]), // 5
-    (5, [30, 37, 37, 37, 24]), // 6
-    (5, [33, 17, 9, 5, 3]),    // 7
-    (5, [26, 37, 37, 37, 26]), // 8
-    (5, [6, 41, 41, 41, 30]),  // 9
-    (1, [20, 0, 0, 0, 0]),     // :
-    (2, [32, 20, 0, 0, 0]),    // ;
-    (3, [8, 20, 34, 0, 0]),    // <
-    (3, [20, 20, 20, 0, 0]),   // =
-    (3, [34, 20, 8, 0, 0]),    // >
-    (5, [2, 1, 41, 5, 2]),     // ?
-    (5, [30, 33, 45, 41, 6]),  // @
-    (5, [62, 9, 9, 9, 62]),    // A
-    (5, [62, 37, 37, 37, 26]), // B
-    (5, [30, 33, 33, 33, 18]), // C
-    (5, [63, 33, 33, 33, 30]), // D
-    (5, [63, 37, 37, 37, 33]), // E
-    (5, [63, 5, 5, 5, 1]),     // F
-    (5, [30, 33, 33, 41, 26]), // G
-    (5, [63, 8, 8, 8, 63]),    // H
-    (5, [33, 33, 63, 33, 33]), // I
-    (5, [17, 33, 33, 31, 1]),  // J
-    (5, [63, 4, 4, 10, 49]),   // K
-    (4, [63, 32, 32, 32, 0]),  // L
-    (5, [63, 2, 4, 2, 63]),    // M
-    (5, [63, 2, 4, 8, 63]),    // N
-    (5, [30, 33, 33, 33, 30]), // O
-    (5, [63, 9, 9, 9, 6]),     // P
-    (5, [30, 33, 41, 17, 46]), // Q
-    (5, [63, 9, 9, 9, 54]),    // R
-    (5, [18, 37, 37, 37, 24]), // S
-    (5, [1, 1, 63, 1, 1]),     // T
-    (5, [31, 32, 32, 32, 31]), // U
-    (5, [7, 24, 32, 24, 7]),   // V
-    (5, [31, 32, 24, 32, 31]), // W
-    (5, [49, 10, 4, 10, 49]),  // X
-    (5, [1, 2, 52, 12, 3]),    // Y
-    (5, [49, 41, 37, 35, 33]), // Z
-    (3, [63, 33, 33, 0, 0]),   // [
-    (3, [3, 12, 48, 0, 0]),    // \
-    (3, [33, 33, 63, 0, 0]),   // ]
-    (5, [4, 2, 1, 2, 4]),      // ^
-    (4, [32, 32, 32, 32, 0]),  // _
-    (2, [1, 2, 0, 0, 0]),      // `
-    (5, [28, 34, 34, 18, 60]), // a
-    (5, [63, 36, 36, 36, 24]), // b
-    (5, [28, 34, 34, 34, 20]), // c
-    (5, [24, 36, 36, 36, 63]), // d
-    (5, [28, 42, 42, 42, 4]),  // e
-    (4, [62, 9, 9, 2, 0]),     // f
-    (5, [4, 42, 42, 42, 28]),  // g
-    (5, [63, 4, 4, 4, 56]),    // h
-    (3, [36, 61, 32, 0, 0]),   // i
-    (4, [16, 32, 32, 29, 0]),  // j
-    (5, [63, 4, 4, 10, 48]),   // k
-    (4, [1, 31, 32, 32, 0]),   // l
-    (5, [62, 2, 60, 2, 60]),   // m
-    (5, [62, 2, 2, 2, 60]),    // n
-    (5, [28, 34, 34, 34, 28]), // o
-    (5, [60, 10, 10, 10, 4]),  // p
-    (5, [4, 10, 10, 10, 60]),  // q
-    (5, [62, 4, 2, 2, 4]),     // r
-    (5, [4, 42, 42, 42, 16]),  // s
-    (3, [4, 63, 4, 0, 0]),     // t
-    (5, [30, 32, 32, 16, 62]), // u
-    (5, [14, 16, 32, 16, 14]), // v
-    (5, [30, 32, 16, 32, 30]), // w
-    (5, [34, 20, 8, 20, 34]),  // x
-    (5, [6, 40, 40, 40, 30]),  // y
-    (5, [34, 50, 42, 38, 34]), // z
-    (3, [18, 45, 33, 0, 0]),   // {
-    (1, [63, 0, 0, 0, 0]),     // |
-    (3, [33, 45, 18, 0, 0]),   // }
-    (5, [2, 1, 2, 4, 2]),      // ~
-    (0, [0, 0, 0, 0, 0]),      // 
+pub const FONT: [(u8, &[u8]); 96] = [
+    (3, &[0, 0, 0]),            //
+    (1, &[47]),                 // !
+    (3, &[3, 0, 3]),            // "
+    (5, &[20, 62, 20, 62, 20]), // #
+    (5, &[4, 42, 63, 42, 16]),  // $
+    (5, &[34, 16, 8, 4, 34]),   // %
+    (5, &[20, 42, 42, 16, 40]), // &
+    (1, &[3]),                  // '
+    (3, &[12, 18, 33]),         // (
+    (3, &[33, 18, 12]),         // )
+    (5, &[20, 8, 62, 8, 20]),   // *
+    (3, &[8, 28, 8]),           // +
+    (2, &[32, 16]),             // ,
+    (3, &[8, 8, 8]),            // -
+    (1, &[32]),                 // .
+    (3, &[48, 12, 3]),          // /
+    (5, &[30, 33, 45, 33, 30]), // 0
+    (3, &[34, 63, 32]),         // 1
+    (5, &[34, 49, 41, 37, 34]), // 2
+    (5, &[18, 33, 37, 37, 26]), // 3
+    (5, &[24, 20, 18, 63, 16]), // 4
+    (5, &[23, 37, 37, 37, 25]), // 5
+    (5, &[30, 37, 37, 37, 24]), // 6
+    (5, &[33, 17, 9, 5, 3]),    // 7
+    (5, &[26, 37, 37, 37, 26]), // 8
+    (5, &[6, 41, 41, 41, 30]),  // 9
+    (1, &[20]),                 // :
+    (2, &[32, 20]),             // ;
+    (3, &[8, 20, 34]),          // <
+    (3, &[20, 20, 20]),         // =
+    (3, &[34, 20, 8]),          // >
+    (5, &[2, 1, 41, 5, 2]),     // ?
+    (5, &[30, 33, 45, 41, 6]),  // @
+    (5, &[62, 9, 9, 9, 62]),    // A
+    (5, &[62, 37, 37, 37, 26]), // B
+    (5, &[30, 33, 33, 33, 18]), // C
+    (5, &[63, 33, 33, 33, 30]), // D
+    (5, &[63, 37, 37, 37, 33]), // E
+    (5, &[63, 5, 5, 5, 1]),     // F
+    (5, &[30, 33, 33, 41, 26]), // G
+    (5, &[63, 8, 8, 8, 63]),    // H
+    (5, &[33, 33, 63, 33, 33]), // I
+    (5, &[17, 33, 33, 31, 1]),  // J
+    (5, &[63, 4, 4, 10, 49]),   // K
+    (4, &[63, 32, 32, 32]),     // L
+    (5, &[63, 2, 4, 2, 63]),    // M
+    (5, &[63, 2, 4, 8, 63]),    // N
+    (5, &[30, 33, 33, 33, 30]), // O
+    (5, &[63, 9, 9, 9, 6]),     // P
+    (5, &[30, 33, 41, 17, 46]), // Q
+    (5, &[63, 9, 9, 9, 54]),    // R
+    (5, &[18, 37, 37, 37, 24]), // S
+    (5, &[1, 1, 63, 1, 1]),     // T
+    (5, &[31, 32, 32, 32, 31]), // U
+    (5, &[7, 24, 32, 24, 7]),   // V
+    (5, &[31, 32, 24, 32, 31]), // W
+    (5, &[49, 10, 4, 10, 49]),  // X
+    (5, &[1, 2, 52, 12, 3]),    // Y
+    (5, &[49, 41, 37, 35, 33]), // Z
+    (3, &[63, 33, 33]),         // [
+    (3, &[3, 12, 48]),          // \
+    (3, &[33, 33, 63]),         // ]
+    (5, &[4, 2, 1, 2, 4]),      // ^
+    (4, &[32, 32, 32, 32]),     // _
+    (2, &[1, 2]),               // `
+    (5, &[28, 34, 34, 18, 60]), // a
+    (5, &[63, 36, 36, 36, 24]), // b
+    (5, &[28, 34, 34, 34, 20]), // c
+    (5, &[24, 36, 36, 36, 63]), // d
+    (5, &[28, 42, 42, 42, 4]),  // e
+    (4, &[62, 9, 9, 2]),        // f
+    (5, &[4, 42, 42, 42, 28]),  // g
+    (5, &[63, 4, 4, 4, 56]),    // h
+    (3, &[36, 61, 32]),         // i
+    (4, &[16, 32, 32, 29]),     // j
+    (5, &[63, 4, 4, 10, 48]),   // k
+    (4, &[1, 31, 32, 32]),      // l
+    (5, &[62, 2, 60, 2, 60]),   // m
+    (5, &[62, 2, 2, 2, 60]),    // n
+    (5, &[28, 34, 34, 34, 28]), // o
+    (5, &[60, 10, 10, 10, 4]),  // p
+    (5, &[4, 10, 10, 10, 60]),  // q
+    (5, &[62, 4, 2, 2, 4]),     // r
+    (5, &[4, 42, 42, 42, 16]),  // s
+    (3, &[4, 63, 4]),           // t
+    (5, &[30, 32, 32, 16, 62]), // u
+    (5, &[14, 16, 32, 16, 14]), // v
+    (5, &[30, 32, 16, 32, 30]), // w
+    (5, &[34, 20, 8, 20, 34]),  // x
+    (5, &[6, 40, 40, 40, 30]),  // y
+    (5, &[34, 50, 42, 38, 34]), // z
+    (3, &[18, 45, 33]),         // {
+    (1, &[63]),                 // |
+    (3, &[33, 45, 18]),         // }
+    (5, &[2, 1, 2, 4, 2]),      // ~
+    (0, &[]),                   // 
 ];
+
+pub const LATIN1: [(u8, &[u8]); 96] = [
+    (3, &[0, 0, 0]),              //
+    (1, &[116]),                  // ¡
+    (4, &[16, 40, 124, 40]),      // ¢
+    (4, &[80, 124, 82, 66]),      // £
+    (5, &[68, 56, 40, 56, 68]),   // ¤
+    (5, &[42, 44, 120, 44, 42]),  // ¥
+    (1, &[102]),                  // ¦
+    (4, &[76, 90, 90, 50]),       // §
+    (3, &[2, 0, 2]),              // ¨
+    (5, &[60, 102, 90, 90, 60]),  // ©
+    (3, &[36, 42, 46]),           // ª
+    (4, &[16, 40, 16, 40]),       // «
+    (4, &[16, 16, 16, 48]),       // ¬
+    (3, &[16, 16, 16]),           //
+    (5, &[60, 66, 106, 86, 60]),  // ®
+    (3, &[2, 2, 2]),              // ¯
+    (3, &[4, 10, 4]),             // °
+    (3, &[72, 92, 72]),           // ±
+    (3, &[18, 26, 20]),           // ²
+    (3, &[18, 22, 10]),           // ³
+    (2, &[4, 2]),                 // ´
+    (4, &[248, 64, 32, 120]),     // µ
+    (4, &[12, 126, 2, 126]),      // ¶
+    (1, &[16]),                   // ·
+    (2, &[128, 64]),              // ¸
+    (2, &[2, 30]),                // ¹
+    (3, &[36, 42, 36]),           // º
+    (4, &[40, 16, 40, 16]),       // »
+    (5, &[44, 16, 8, 52, 96]),    // ¼
+    (5, &[44, 16, 8, 116, 80]),   // ½
+    (5, &[76, 44, 16, 40, 116]),  // ¾
+    (3, &[32, 84, 64]),           // ¿
+    (4, &[124, 21, 22, 124]),     // À
+    (4, &[124, 22, 21, 124]),     // Á
+    (4, &[126, 21, 22, 124]),     // Â
+    (4, &[126, 21, 22, 125]),     // Ã
+    (4, &[125, 20, 21, 124]),     // Ä
+    (4, &[124, 23, 23, 124]),     // Å
+    (5, &[124, 18, 126, 74, 74]), // Æ
+    (5, &[60, 194, 66, 66, 36]),  // Ç
+    (4, &[124, 85, 86, 68]),      // È
+    (4, &[124, 86, 85, 68]),      // É
+    (4, &[126, 85, 86, 68]),      // Ê
+    (4, &[125, 84, 85, 68]),      // Ë
+    (3, &[69, 126, 68]),          // Ì
+    (3, &[70, 125, 68]),          // Í
+    (3, &[70, 125, 70]),          // Î
+    (3, &[69, 124, 69]),          // Ï
+    (5, &[74, 126, 74, 66, 60]),  // Ð
+    (5, &[126, 9, 18, 33, 124]),  // Ñ
+    (4, &[56, 69, 70, 56]),       // Ò
+    (4, &[56, 70, 69, 56]),       // Ó
+    (4, &[58, 69, 70, 56]),       // Ô
+    (4, &[58, 69, 70, 57]),       // Õ
+    (4, &[57, 68, 69, 56]),       // Ö
+    (3, &[40, 16, 40]),           // ×
+    (5, &[124, 98, 90, 70, 62]),  // Ø
+    (4, &[60, 65, 66, 60]),       // Ù
+    (4, &[60, 66, 65, 60]),       // Ú
+    (4, &[62, 65, 66, 60]),       // Û
+    (4, &[61, 64, 65, 60]),       // Ü
+    (5, &[4, 10, 113, 8, 4]),     // Ý
+    (4, &[126, 36, 36, 24]),      // Þ
+    (4, &[124, 2, 74, 52]),       // ß
+    (5, &[56, 69, 70, 36, 120]),  // à
+    (5, &[56, 70, 69, 36, 120]),  // á
+    (5, &[56, 70, 69, 38, 120]),  // â
+    (5, &[58, 69, 70, 37, 120]),  // ã
+    (5, &[56, 69, 68, 37, 120]),  // ä
+    (5, &[56, 71, 71, 36, 120]),  // å
+    (5, &[36, 84, 56, 84, 88]),   // æ
+    (5, &[56, 196, 68, 68, 40]),  // ç
+    (5, &[56, 85, 86, 84, 8]),    // è
+    (5, &[56, 86, 85, 84, 8]),    // é
+    (5, &[56, 86, 85, 86, 8]),    // ê
+    (5, &[56, 85, 84, 85, 8]),    // ë
+    (3, &[73, 122, 64]),          // ì
+    (3, &[74, 121, 64]),          // í
+    (3, &[74, 121, 66]),          // î
+    (3, &[73, 120, 65]),          // ï
+    (4, &[48, 74, 76, 58]),       // ð
+    (5, &[126, 5, 6, 5, 120]),    // ñ
+    (5, &[56, 69, 70, 68, 56]),   // ò
+    (5, &[56, 70, 69, 68, 56]),   // ó
+    (5, &[56, 70, 69, 70, 56]),   // ô
+    (5, &[58, 69, 70, 69, 56]),   // õ
+    (5, &[56, 69, 68, 69, 56]),   // ö
+    (3, &[16, 84, 16]),           // ÷
+    (5, &[120, 100, 84, 76, 60]), // ø
+    (5, &[60, 65, 66, 32, 124]),  // ù
+    (5, &[60, 66, 65, 32, 124]),  // ú
+    (5, &[60, 66, 65, 34, 124]),  // û
+    (5, &[60, 65, 64, 33, 124]),  // ü
+    (5, &[12, 82, 81, 80, 60]),   // ý
+    (4, &[254, 72, 72, 48]),      // þ
+    (5, &[12, 81, 80, 81, 60]),   // ÿ
+];
+
+pub const ELLIPSIS: [(u8, &[u8]); 1] = [
+    (5, &[32, 0, 32, 0, 32]), // …
+];
+
+pub const ARROWS: [(u8, &[u8]); 4] = [
+    (5, &[8, 28, 42, 8, 8]),  // ←
+    (5, &[8, 4, 62, 4, 8]),   // ↑
+    (5, &[8, 8, 42, 28, 8]),  // →
+    (5, &[8, 16, 62, 16, 8]), // ↓
+];
+
+pub const SHAPES: [(u8, &[u8]); 48] = [
+    (4, &[60, 60, 60, 60]),     // ■
+    (4, &[60, 36, 36, 60]),     // □
+    (0, &[]),                   //
+    (0, &[]),                   //
+    (0, &[]),                   //
+    (0, &[]),                   //
+    (0, &[]),                   //
+    (0, &[]),                   //
+    (0, &[]),                   //
+    (0, &[]),                   //
+    (0, &[]),                   //
+    (0, &[]),                   //
+    (0, &[]),                   //
+    (0, &[]),                   //
+    (0, &[]),                   //
+    (0, &[]),                   //
+    (0, &[]),                   //
+    (0, &[]),                   //
+    (5, &[16, 24, 28, 24, 16]), // ▲
+    (0, &[]),                   //
+    (0, &[]),                   //
+    (0, &[]),                   //
+    (3, &[62, 28, 8]),          // ▶
+    (0, &[]),                   //
+    (0, &[]),                   //
+    (0, &[]),                   //
+    (0, &[]),                   //
+    (0, &[]),                   //
+    (5, &[4, 12, 28, 12, 4]),   // ▼
+    (0, &[]),                   //
+    (0, &[]),                   //
+    (0, &[]),                   //
+    (3, &[8, 28, 62]),          // ◀
+    (0, &[]),                   //
+    (0, &[]),                   //
+    (0, &[]),                   //
+    (0, &[]),                   //
+    (0, &[]),                   //
+    (0, &[]),                   //
+    (0, &[]),                   //
+    (0, &[]),                   //
+    (0, &[]),                   //
+    (0, &[]),                   //
+    (5, &[28, 34, 34, 34, 28]), // ○
+    (0, &[]),                   //
+    (0, &[]),                   //
+    (0, &[]),                   //
+    (5, &[28, 62, 62, 62, 28]), // ●
+];
+
+pub const MUSIC: [(u8, &[u8]); 6] = [
+    (4, &[48, 48, 30, 4]),      // ♪
+    (5, &[48, 62, 2, 50, 62]),  // ♫
+    (5, &[48, 62, 6, 54, 62]),  // ♬
+    (3, &[62, 40, 16]),         // ♭
+    (3, &[30, 20, 60]),         // ♮
+    (5, &[20, 62, 20, 62, 20]), // ♯
+];
+
+pub const DINGBATS: [(u8, &[u8]); 1] = [
+    (5, &[8, 16, 8, 4, 2]), // ✓
+];
+
+pub const REPLACEMENT: (u8, &[u8]) = (5, &[63, 62, 18, 56, 63]);
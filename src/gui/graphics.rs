@@ -0,0 +1,835 @@
+///
+/// # Graphics primitives
+///
+/// Lines, rectangles, circles, arcs, triangles, polygons and text drawn on any
+/// `Canvas`, ported from the Adafruit GFX library. Coordinates are signed so shapes can be
+/// partially off the canvas, anything outside of the canvas is clipped.
+///
+/// Only integer math is used so the primitives are available with `no_std`.
+///
+use super::display::Canvas;
+use super::font::Font;
+use alloc::vec::Vec;
+use core::mem::swap;
+
+/// Quarter sine wave in 1 degree steps, scaled by 1024
+const SINE: [i32; 91] = [
+    0, 18, 36, 54, 71, 89, 107, 125, 143, 160, 178, 195, 213, 230, 248, 265, 282, 299, 316, 333,
+    350, 367, 384, 400, 416, 433, 449, 465, 481, 496, 512, 527, 543, 558, 573, 587, 602, 616, 630,
+    644, 658, 672, 685, 698, 711, 724, 737, 749, 761, 773, 784, 796, 807, 818, 828, 839, 849, 859,
+    868, 878, 887, 896, 904, 912, 920, 928, 935, 943, 949, 956, 962, 968, 974, 979, 984, 989, 994,
+    998, 1002, 1005, 1008, 1011, 1014, 1016, 1018, 1020, 1022, 1023, 1023, 1024, 1024,
+];
+
+/// Corners of a circle used when drawing rounded rectangles
+const TOP_LEFT: u8 = 0x1;
+const TOP_RIGHT: u8 = 0x2;
+const BOTTOM_RIGHT: u8 = 0x4;
+const BOTTOM_LEFT: u8 = 0x8;
+
+///
+/// Shape drawing, available on every canvas
+///
+pub trait Graphics<T: Clone>: Canvas<T> {
+    ///
+    /// Set a pixel, coordinates outside of the canvas are ignored
+    ///
+    fn plot(&mut self, x: i32, y: i32, colour: T) {
+        if x >= 0 && y >= 0 && (x as usize) < self.width() && (y as usize) < self.height() {
+            self.set_pixel(x as usize, y as usize, colour);
+        }
+    }
+
+    ///
+    /// Draw a line between two points (Bresenham's algorithm)
+    ///
+    fn draw_line(&mut self, x0: i32, y0: i32, x1: i32, y1: i32, colour: T) {
+        if y0 == y1 {
+            self.draw_hline(x0.min(x1), y0, (x1 - x0).abs() + 1, colour);
+            return;
+        }
+        if x0 == x1 {
+            self.draw_vline(x0, y0.min(y1), (y1 - y0).abs() + 1, colour);
+            return;
+        }
+
+        let (dx, dy) = ((x1 - x0).abs(), -(y1 - y0).abs());
+        let (sx, sy) = ((x1 - x0).signum(), (y1 - y0).signum());
+        let (mut x, mut y) = (x0, y0);
+        let mut err = dx + dy;
+        loop {
+            self.plot(x, y, colour.clone());
+            if x == x1 && y == y1 {
+                break;
+            }
+            let e2 = 2 * err;
+            if e2 >= dy {
+                err += dy;
+                x += sx;
+            }
+            if e2 <= dx {
+                err += dx;
+                y += sy;
+            }
+        }
+    }
+
+    ///
+    /// Draw the outline of a rectangle
+    ///
+    fn draw_rect(&mut self, x: i32, y: i32, w: i32, h: i32, colour: T) {
+        if w <= 0 || h <= 0 {
+            return;
+        }
+        self.draw_hline(x, y, w, colour.clone());
+        self.draw_hline(x, y + h - 1, w, colour.clone());
+        self.draw_vline(x, y, h, colour.clone());
+        self.draw_vline(x + w - 1, y, h, colour);
+    }
+
+    ///
+    /// Draw the outline of a rectangle with rounded corners of radius `r`
+    ///
+    fn draw_round_rect(&mut self, x: i32, y: i32, w: i32, h: i32, r: i32, colour: T) {
+        if w <= 0 || h <= 0 {
+            return;
+        }
+        let r = r.clamp(0, w.min(h) / 2);
+        self.draw_hline(x + r, y, w - 2 * r, colour.clone());
+        self.draw_hline(x + r, y + h - 1, w - 2 * r, colour.clone());
+        self.draw_vline(x, y + r, h - 2 * r, colour.clone());
+        self.draw_vline(x + w - 1, y + r, h - 2 * r, colour.clone());
+
+        draw_corners(self, x + r, y + r, r, TOP_LEFT, colour.clone());
+        draw_corners(self, x + w - r - 1, y + r, r, TOP_RIGHT, colour.clone());
+        draw_corners(
+            self,
+            x + w - r - 1,
+            y + h - r - 1,
+            r,
+            BOTTOM_RIGHT,
+            colour.clone(),
+        );
+        draw_corners(self, x + r, y + h - r - 1, r, BOTTOM_LEFT, colour);
+    }
+
+    ///
+    /// Fill a rectangle with rounded corners of radius `r`
+    ///
+    fn fill_round_rect(&mut self, x: i32, y: i32, w: i32, h: i32, r: i32, colour: T) {
+        if w <= 0 || h <= 0 {
+            return;
+        }
+        let r = r.clamp(0, w.min(h) / 2);
+        self.fill_rect(x + r, y, w - 2 * r, h, colour.clone());
+        fill_corners(
+            self,
+            x + w - r - 1,
+            y + r,
+            r,
+            true,
+            h - 2 * r - 1,
+            colour.clone(),
+        );
+        fill_corners(self, x + r, y + r, r, false, h - 2 * r - 1, colour);
+    }
+
+    ///
+    /// Draw the outline of a circle (midpoint algorithm)
+    ///
+    fn draw_circle(&mut self, cx: i32, cy: i32, r: i32, colour: T) {
+        if r < 0 {
+            return;
+        }
+        self.plot(cx, cy + r, colour.clone());
+        self.plot(cx, cy - r, colour.clone());
+        self.plot(cx + r, cy, colour.clone());
+        self.plot(cx - r, cy, colour.clone());
+        draw_corners(
+            self,
+            cx,
+            cy,
+            r,
+            TOP_LEFT | TOP_RIGHT | BOTTOM_RIGHT | BOTTOM_LEFT,
+            colour,
+        );
+    }
+
+    ///
+    /// Fill a circle
+    ///
+    fn fill_circle(&mut self, cx: i32, cy: i32, r: i32, colour: T) {
+        if r < 0 {
+            return;
+        }
+        self.draw_vline(cx, cy - r, 2 * r + 1, colour.clone());
+        fill_corners(self, cx, cy, r, true, 0, colour.clone());
+        fill_corners(self, cx, cy, r, false, 0, colour);
+    }
+
+    ///
+    /// Draw part of the outline of a circle
+    ///
+    /// Angles are in degrees clockwise from 3 o'clock, the arc is drawn clockwise
+    /// from `start` to `end`.
+    ///
+    fn draw_arc(&mut self, cx: i32, cy: i32, r: i32, start: i32, end: i32, colour: T) {
+        if r < 0 {
+            return;
+        }
+        let sweep = (end - start).rem_euclid(360);
+        if sweep == 0 && start != end {
+            self.draw_circle(cx, cy, r, colour);
+            return;
+        }
+        let (from, to) = (direction(start), direction(end));
+        let in_arc = |x: i32, y: i32| {
+            let after_start = cross(from, (x, y)) >= 0;
+            let before_end = cross((x, y), to) >= 0;
+            if sweep <= 180 {
+                after_start && before_end
+            } else {
+                after_start || before_end
+            }
+        };
+
+        let plot = |canvas: &mut Self, x: i32, y: i32| {
+            if in_arc(x, y) {
+                canvas.plot(cx + x, cy + y, colour.clone());
+            }
+        };
+        circle_points(r, |x, y| {
+            plot(self, x, y);
+            plot(self, -x, y);
+            plot(self, x, -y);
+            plot(self, -x, -y);
+            plot(self, y, x);
+            plot(self, -y, x);
+            plot(self, y, -x);
+            plot(self, -y, -x);
+        });
+        for (x, y) in [(r, 0), (-r, 0), (0, r), (0, -r)] {
+            plot(self, x, y);
+        }
+    }
+
+    ///
+    /// Draw the outline of a triangle
+    ///
+    fn draw_triangle(&mut self, p0: (i32, i32), p1: (i32, i32), p2: (i32, i32), colour: T) {
+        self.draw_line(p0.0, p0.1, p1.0, p1.1, colour.clone());
+        self.draw_line(p1.0, p1.1, p2.0, p2.1, colour.clone());
+        self.draw_line(p2.0, p2.1, p0.0, p0.1, colour);
+    }
+
+    ///
+    /// Fill a triangle, drawn as horizontal spans
+    ///
+    fn fill_triangle(&mut self, p0: (i32, i32), p1: (i32, i32), p2: (i32, i32), colour: T) {
+        // Sort points by y (p0 at the top)
+        let (mut p0, mut p1, mut p2) = (p0, p1, p2);
+        if p0.1 > p1.1 {
+            swap(&mut p0, &mut p1);
+        }
+        if p1.1 > p2.1 {
+            swap(&mut p1, &mut p2);
+        }
+        if p0.1 > p1.1 {
+            swap(&mut p0, &mut p1);
+        }
+
+        if p0.1 == p2.1 {
+            // All on the same line
+            let left = p0.0.min(p1.0).min(p2.0);
+            let right = p0.0.max(p1.0).max(p2.0);
+            self.draw_hline(left, p0.1, right - left + 1, colour);
+            return;
+        }
+
+        // Interpolate the x of the long edge (p0-p2) and the short edges
+        // (p0-p1 then p1-p2) for each line
+        let span = |a: (i32, i32), b: (i32, i32), y: i32| {
+            if b.1 == a.1 {
+                a.0
+            } else {
+                a.0 + (b.0 - a.0) * (y - a.1) / (b.1 - a.1)
+            }
+        };
+        for y in p0.1..=p2.1 {
+            let long = span(p0, p2, y);
+            let short = if y < p1.1 || p1.1 == p2.1 {
+                span(p0, p1, y)
+            } else {
+                span(p1, p2, y)
+            };
+            let (left, right) = (long.min(short), long.max(short));
+            self.draw_hline(left, y, right - left + 1, colour.clone());
+        }
+
+        // Spans only have one x per edge, so shallow edges need the outline
+        self.draw_triangle(p0, p1, p2, colour);
+    }
+
+    ///
+    /// Draw the outline of a polygon, the last point is joined to the first
+    ///
+    fn draw_polygon(&mut self, points: &[(i32, i32)], colour: T) {
+        for (idx, a) in points.iter().enumerate() {
+            let b = points[(idx + 1) % points.len()];
+            self.draw_line(a.0, a.1, b.0, b.1, colour.clone());
+        }
+    }
+
+    ///
+    /// Fill a polygon (even-odd rule), drawn as horizontal spans
+    ///
+    fn fill_polygon(&mut self, points: &[(i32, i32)], colour: T) {
+        if points.len() < 3 {
+            self.draw_polygon(points, colour);
+            return;
+        }
+        let top = points.iter().map(|p| p.1).min().unwrap_or(0).max(0);
+        let bottom = points
+            .iter()
+            .map(|p| p.1)
+            .max()
+            .unwrap_or(0)
+            .min(self.height() as i32 - 1);
+
+        let mut crossings: Vec<i32> = Vec::new();
+        for y in top..=bottom {
+            crossings.clear();
+            for (idx, a) in points.iter().enumerate() {
+                let b = points[(idx + 1) % points.len()];
+                // Half open so shared vertices are only counted once
+                if (a.1 <= y && b.1 > y) || (b.1 <= y && a.1 > y) {
+                    crossings.push(a.0 + (b.0 - a.0) * (y - a.1) / (b.1 - a.1));
+                }
+            }
+            crossings.sort_unstable();
+            for pair in crossings.chunks_exact(2) {
+                self.draw_hline(pair[0], y, pair[1] - pair[0] + 1, colour.clone());
+            }
+        }
+
+        // Fill misses the bottom edges, so include the outline
+        self.draw_polygon(points, colour);
+    }
+
+    ///
+    /// Draw a character with its top left corner at (x, y), returns the width of
+    /// the character including spacing
+    ///
+    /// With a `background` colour the character cell (including spacing) is
+    /// filled first, otherwise only the pixels of the glyph are drawn so the
+    /// character can be placed over other graphics.
+    ///
+    fn draw_char(
+        &mut self,
+        x: i32,
+        y: i32,
+        c: char,
+        font: &dyn Font,
+        colour: T,
+        background: Option<T>,
+    ) -> i32 {
+        let glyph = match font.glyph_or_replacement(c) {
+            Some(glyph) => glyph,
+            None => return 0,
+        };
+        let advance = font.advance(c) as i32;
+
+        if let Some(background) = background {
+            // Cover any accents above the font as well as the line of spacing below
+            let top = glyph.top.min(0);
+            let bottom = (font.height() as i32 + 1).max(glyph.top + glyph.height as i32);
+            self.fill_rect(x, y + top, advance, bottom - top, background);
+        }
+        for dy in 0..glyph.height {
+            for dx in 0..glyph.width {
+                if glyph.pixel(dx, dy) {
+                    let py = y + glyph.top + dy as i32;
+                    self.plot(x + dx as i32, py, colour.clone());
+                }
+            }
+        }
+
+        advance
+    }
+
+    ///
+    /// Draw text with its top left corner at (x, y), newlines start a new line
+    /// at `x`. Returns the width of the last line drawn.
+    ///
+    /// See `draw_char` for background handling.
+    ///
+    fn draw_text(
+        &mut self,
+        x: i32,
+        y: i32,
+        s: &str,
+        font: &dyn Font,
+        colour: T,
+        background: Option<T>,
+    ) -> i32 {
+        let (mut cx, mut cy) = (x, y);
+        for c in s.chars() {
+            match c {
+                '\n' => {
+                    cx = x;
+                    cy += font.line_spacing() as i32;
+                }
+                _ => cx += self.draw_char(cx, cy, c, font, colour.clone(), background.clone()),
+            }
+        }
+        cx - x
+    }
+}
+
+impl<T: Clone, C: Canvas<T> + ?Sized> Graphics<T> for C {}
+
+/// Call `f` with each point of the first octant of a circle, excluding the axes
+fn circle_points<F: FnMut(i32, i32)>(r: i32, mut f: F) {
+    let mut err = 1 - r;
+    let (mut dx, mut dy) = (1, -2 * r);
+    let (mut x, mut y) = (0, r);
+    while x < y {
+        if err >= 0 {
+            y -= 1;
+            dy += 2;
+            err += dy;
+        }
+        x += 1;
+        dx += 2;
+        err += dx;
+        f(x, y);
+    }
+}
+
+/// Draw quarters of a circle outline selected by the corner mask
+fn draw_corners<T: Clone, C: Canvas<T> + ?Sized>(
+    canvas: &mut C,
+    cx: i32,
+    cy: i32,
+    r: i32,
+    corners: u8,
+    colour: T,
+) {
+    circle_points(r, |x, y| {
+        if corners & TOP_LEFT != 0 {
+            canvas.plot(cx - y, cy - x, colour.clone());
+            canvas.plot(cx - x, cy - y, colour.clone());
+        }
+        if corners & TOP_RIGHT != 0 {
+            canvas.plot(cx + x, cy - y, colour.clone());
+            canvas.plot(cx + y, cy - x, colour.clone());
+        }
+        if corners & BOTTOM_RIGHT != 0 {
+            canvas.plot(cx + x, cy + y, colour.clone());
+            canvas.plot(cx + y, cy + x, colour.clone());
+        }
+        if corners & BOTTOM_LEFT != 0 {
+            canvas.plot(cx - y, cy + x, colour.clone());
+            canvas.plot(cx - x, cy + y, colour.clone());
+        }
+    });
+}
+
+/// Fill the left or right half of a circle, stretched vertically by `delta`
+fn fill_corners<T: Clone, C: Canvas<T> + ?Sized>(
+    canvas: &mut C,
+    cx: i32,
+    cy: i32,
+    r: i32,
+    right: bool,
+    delta: i32,
+    colour: T,
+) {
+    let sign = if right { 1 } else { -1 };
+    let (mut px, mut py) = (0, r);
+    circle_points(r, |x, y| {
+        // Avoid drawing the same column twice (matters for XOR style drawing)
+        if x < y + 1 {
+            canvas.draw_vline(cx + sign * x, cy - y, 2 * y + 1 + delta, colour.clone());
+        }
+        if y != py {
+            canvas.draw_vline(cx + sign * py, cy - px, 2 * px + 1 + delta, colour.clone());
+            py = y;
+        }
+        px = x;
+    });
+}
+
+/// Cross product of two vectors (positive if `b` is clockwise of `a` on screen)
+fn cross(a: (i32, i32), b: (i32, i32)) -> i32 {
+    a.0 * b.1 - a.1 * b.0
+}
+
+/// Unit vector (scaled by 1024) of an angle in degrees
+fn direction(angle: i32) -> (i32, i32) {
+    let angle = angle.rem_euclid(360);
+    let sin = |a: i32| match a {
+        0..=90 => SINE[a as usize],
+        91..=180 => SINE[(180 - a) as usize],
+        181..=270 => -SINE[(a - 180) as usize],
+        _ => -SINE[(360 - a) as usize],
+    };
+    (sin((angle + 90) % 360), sin(angle))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gui::display::{MonochromeCanvas, Pixel};
+    use crate::gui::font::{Glyph, NX5};
+
+    fn canvas() -> MonochromeCanvas {
+        MonochromeCanvas::new(16, 16)
+    }
+
+    fn is_on(canvas: &dyn Canvas<Pixel>, x: usize, y: usize) -> bool {
+        matches!(canvas.pixel(x, y), Some(Pixel::On))
+    }
+
+    /// Coordinates of every pixel that is on
+    fn lit(canvas: &dyn Canvas<Pixel>) -> Vec<(usize, usize)> {
+        let mut pixels = Vec::new();
+        for y in 0..canvas.height() {
+            for x in 0..canvas.width() {
+                if is_on(canvas, x, y) {
+                    pixels.push((x, y));
+                }
+            }
+        }
+        pixels
+    }
+
+    /// Every pixel of the outline is also part of the fill
+    fn contains(fill: &MonochromeCanvas, outline: &MonochromeCanvas) -> bool {
+        lit(outline).iter().all(|&(x, y)| is_on(fill, x, y))
+    }
+
+    #[test]
+    fn line_includes_both_endpoints() {
+        for &(x0, y0, x1, y1) in &[(1, 2, 12, 7), (12, 7, 1, 2), (3, 14, 5, 1), (5, 1, 3, 14)] {
+            let mut canvas = canvas();
+            canvas.draw_line(x0, y0, x1, y1, Pixel::On);
+            assert!(is_on(&canvas, x0 as usize, y0 as usize));
+            assert!(is_on(&canvas, x1 as usize, y1 as usize));
+            // One pixel per step along the major axis
+            let steps = (x1 - x0).abs().max((y1 - y0).abs()) as usize;
+            assert_eq!(lit(&canvas).len(), steps + 1);
+        }
+    }
+
+    #[test]
+    fn horizontal_and_vertical_lines_in_either_direction() {
+        let mut forward = canvas();
+        forward.draw_line(2, 3, 9, 3, Pixel::On);
+        forward.draw_line(4, 5, 4, 12, Pixel::On);
+        let mut backward = canvas();
+        backward.draw_line(9, 3, 2, 3, Pixel::On);
+        backward.draw_line(4, 12, 4, 5, Pixel::On);
+        assert_eq!(lit(&forward), lit(&backward));
+        assert_eq!(lit(&forward).len(), 8 + 8);
+    }
+
+    #[test]
+    fn circle_is_symmetric() {
+        let mut outline = canvas();
+        outline.draw_circle(7, 7, 5, Pixel::On);
+        let mut fill = canvas();
+        fill.fill_circle(7, 7, 5, Pixel::On);
+
+        for canvas in [&outline, &fill] {
+            for (x, y) in lit(canvas) {
+                assert!(is_on(canvas, 14 - x, y));
+                assert!(is_on(canvas, x, 14 - y));
+                assert!(is_on(canvas, y, x));
+            }
+            for &(x, y) in &[(12, 7), (2, 7), (7, 2), (7, 12)] {
+                assert!(is_on(canvas, x, y));
+            }
+            assert!(!is_on(canvas, 13, 7));
+            assert!(!is_on(canvas, 12, 12));
+        }
+        assert!(!is_on(&outline, 7, 7));
+        assert!(is_on(&fill, 7, 7));
+        assert!(contains(&fill, &outline));
+    }
+
+    #[test]
+    fn arc_is_drawn_clockwise_between_angles() {
+        let mut canvas = canvas();
+        canvas.draw_arc(7, 7, 5, 0, 90, Pixel::On);
+        assert!(is_on(&canvas, 12, 7));
+        assert!(is_on(&canvas, 7, 12));
+        assert!(!is_on(&canvas, 2, 7));
+        assert!(!is_on(&canvas, 7, 2));
+        assert!(lit(&canvas).iter().all(|&(x, y)| x >= 7 && y >= 7));
+
+        // Wrapping past 0 degrees
+        let mut wrapped = self::canvas();
+        wrapped.draw_arc(7, 7, 5, 270, 90, Pixel::On);
+        assert!(is_on(&wrapped, 7, 2));
+        assert!(is_on(&wrapped, 12, 7));
+        assert!(is_on(&wrapped, 7, 12));
+        assert!(!is_on(&wrapped, 2, 7));
+
+        // A full turn is the whole circle
+        let mut full = self::canvas();
+        full.draw_arc(7, 7, 5, 0, 360, Pixel::On);
+        let mut circle = self::canvas();
+        circle.draw_circle(7, 7, 5, Pixel::On);
+        assert_eq!(lit(&full), lit(&circle));
+    }
+
+    #[test]
+    fn rect_fill_and_outline() {
+        let mut outline = canvas();
+        outline.draw_rect(2, 3, 6, 4, Pixel::On);
+        let mut fill = canvas();
+        fill.fill_rect(2, 3, 6, 4, Pixel::On);
+
+        assert_eq!(lit(&outline).len(), 2 * 6 + 2 * 2);
+        assert_eq!(lit(&fill).len(), 6 * 4);
+        assert!(is_on(&outline, 7, 6));
+        assert!(!is_on(&outline, 4, 4));
+        assert!(is_on(&fill, 4, 4));
+        assert!(contains(&fill, &outline));
+    }
+
+    #[test]
+    fn round_rect_fill_and_outline() {
+        let mut outline = canvas();
+        outline.draw_round_rect(1, 2, 12, 9, 3, Pixel::On);
+        let mut fill = canvas();
+        fill.fill_round_rect(1, 2, 12, 9, 3, Pixel::On);
+
+        for canvas in [&outline, &fill] {
+            // Corners are cut, edges are straight between them
+            for &(x, y) in &[(1, 2), (12, 2), (1, 10), (12, 10)] {
+                assert!(!is_on(canvas, x, y));
+            }
+            for &(x, y) in &[(6, 2), (6, 10), (1, 6), (12, 6)] {
+                assert!(is_on(canvas, x, y));
+            }
+            // Symmetric about the centre
+            for (x, y) in lit(canvas) {
+                assert!(is_on(canvas, 13 - x, y));
+                assert!(is_on(canvas, x, 12 - y));
+            }
+            assert!(lit(canvas)
+                .iter()
+                .all(|&(x, y)| (1..=12).contains(&x) && (2..=10).contains(&y)));
+        }
+        assert!(!is_on(&outline, 6, 6));
+        assert!(is_on(&fill, 6, 6));
+        assert!(contains(&fill, &outline));
+
+        // No radius is a plain rectangle
+        let mut square = canvas();
+        square.draw_round_rect(1, 2, 12, 9, 0, Pixel::On);
+        let mut rect = canvas();
+        rect.draw_rect(1, 2, 12, 9, Pixel::On);
+        assert_eq!(lit(&square), lit(&rect));
+    }
+
+    #[test]
+    fn triangle_fill_and_outline() {
+        let (p0, p1, p2) = ((2, 1), (13, 6), (4, 13));
+        let mut outline = canvas();
+        outline.draw_triangle(p0, p1, p2, Pixel::On);
+        let mut fill = canvas();
+        fill.fill_triangle(p2, p0, p1, Pixel::On);
+
+        for canvas in [&outline, &fill] {
+            for &(x, y) in &[p0, p1, p2] {
+                assert!(is_on(canvas, x as usize, y as usize));
+            }
+            assert!(!is_on(canvas, 13, 13));
+            assert!(!is_on(canvas, 1, 1));
+        }
+        assert!(!is_on(&outline, 6, 7));
+        assert!(is_on(&fill, 6, 7));
+        assert!(contains(&fill, &outline));
+
+        // Degenerate triangle on a single line
+        let mut flat = self::canvas();
+        flat.fill_triangle((9, 4), (2, 4), (5, 4), Pixel::On);
+        assert_eq!(lit(&flat).len(), 8);
+    }
+
+    #[test]
+    fn polygon_fill_and_outline() {
+        let square = [(2, 3), (9, 3), (9, 8), (2, 8)];
+        let mut outline = canvas();
+        outline.draw_polygon(&square, Pixel::On);
+        let mut rect = canvas();
+        rect.draw_rect(2, 3, 8, 6, Pixel::On);
+        assert_eq!(lit(&outline), lit(&rect));
+
+        let mut fill = canvas();
+        fill.fill_polygon(&square, Pixel::On);
+        let mut filled_rect = canvas();
+        filled_rect.fill_rect(2, 3, 8, 6, Pixel::On);
+        assert_eq!(lit(&fill), lit(&filled_rect));
+
+        // Concave "U" shape, the notch is left empty
+        let shape = [
+            (1, 1),
+            (4, 1),
+            (4, 8),
+            (10, 8),
+            (10, 1),
+            (13, 1),
+            (13, 12),
+            (1, 12),
+        ];
+        let mut fill = canvas();
+        fill.fill_polygon(&shape, Pixel::On);
+        let mut outline = canvas();
+        outline.draw_polygon(&shape, Pixel::On);
+        assert!(is_on(&fill, 2, 4));
+        assert!(is_on(&fill, 12, 4));
+        assert!(is_on(&fill, 7, 10));
+        assert!(!is_on(&fill, 7, 4));
+        assert!(!is_on(&outline, 2, 4));
+        assert!(contains(&fill, &outline));
+    }
+
+    #[test]
+    fn shapes_are_clipped_to_the_canvas() {
+        let mut canvas = canvas();
+        canvas.fill_rect(-5, -5, 8, 8, Pixel::On);
+        assert_eq!(lit(&canvas).len(), 3 * 3);
+        assert!(is_on(&canvas, 2, 2));
+
+        let mut canvas = self::canvas();
+        canvas.draw_line(-10, -10, 25, 25, Pixel::On);
+        assert_eq!(lit(&canvas).len(), 16);
+        assert!(is_on(&canvas, 0, 0));
+        assert!(is_on(&canvas, 15, 15));
+
+        // Only the part of the circle on the canvas
+        let mut canvas = self::canvas();
+        canvas.draw_circle(0, 0, 5, Pixel::On);
+        assert!(is_on(&canvas, 5, 0));
+        assert!(is_on(&canvas, 0, 5));
+        assert!(lit(&canvas).iter().all(|&(x, y)| x <= 5 && y <= 5));
+
+        let mut canvas = self::canvas();
+        canvas.fill_triangle((-20, -4), (30, 3), (8, 40), Pixel::On);
+        canvas.fill_polygon(&[(-8, -8), (40, 2), (3, 30)], Pixel::On);
+        canvas.fill_round_rect(-4, 10, 30, 20, 6, Pixel::On);
+        canvas.draw_arc(20, -3, 9, 45, 200, Pixel::On);
+        assert!(is_on(&canvas, 8, 8));
+    }
+
+    #[test]
+    fn empty_and_negative_sizes_draw_nothing() {
+        let mut canvas = canvas();
+        canvas.draw_rect(4, 4, -3, 5, Pixel::On);
+        canvas.draw_round_rect(4, 4, -3, 5, 2, Pixel::On);
+        canvas.draw_round_rect(4, 4, 5, -3, 2, Pixel::On);
+        canvas.fill_round_rect(4, 4, -3, 5, 2, Pixel::On);
+        canvas.fill_round_rect(4, 4, 5, 0, 2, Pixel::On);
+        canvas.draw_circle(4, 4, -1, Pixel::On);
+        canvas.fill_circle(4, 4, -1, Pixel::On);
+        canvas.draw_arc(4, 4, -1, 0, 90, Pixel::On);
+        canvas.fill_polygon(&[], Pixel::On);
+        assert!(lit(&canvas).is_empty());
+    }
+
+    /// Every pixel of the glyph drawn at (x, y) that is on the canvas matches
+    fn shows_glyph(canvas: &MonochromeCanvas, x: i32, y: i32, glyph: &Glyph) -> bool {
+        (0..glyph.height).all(|dy| {
+            (0..glyph.width).all(|dx| {
+                let (px, py) = (x + dx as i32, y + glyph.top + dy as i32);
+                if px < 0 || py < 0 || px >= 16 || py >= 16 {
+                    return true;
+                }
+                is_on(canvas, px as usize, py as usize) == glyph.pixel(dx, dy)
+            })
+        })
+    }
+
+    #[test]
+    fn char_background_is_transparent_or_opaque() {
+        let glyph = NX5.glyph('A').unwrap();
+        let advance = NX5.advance('A') as i32;
+
+        // Only the pixels of the glyph are changed
+        let mut transparent = canvas();
+        transparent.fill_rect(0, 0, 16, 16, Pixel::On);
+        let width = transparent.draw_char(2, 3, 'A', &NX5, Pixel::Off, None);
+        assert_eq!(width, advance);
+        for y in 0..16 {
+            for x in 0..16 {
+                let in_glyph = x >= 2 && y >= 3 && glyph.pixel(x - 2, y - 3);
+                assert_eq!(is_on(&transparent, x, y), !in_glyph);
+            }
+        }
+
+        let mut opaque = canvas();
+        opaque.fill_rect(0, 0, 16, 16, Pixel::On);
+        opaque.draw_char(2, 3, 'A', &NX5, Pixel::On, Some(Pixel::Off));
+        assert!(shows_glyph(&opaque, 2, 3, &glyph));
+        // The cell is the advance wide and a line of spacing below the font
+        let height = NX5.height() as i32 + 1;
+        for (x, y) in lit(&opaque) {
+            let (x, y) = (x as i32, y as i32);
+            let in_cell = (2..2 + advance).contains(&x) && (3..3 + height).contains(&y);
+            assert!(!in_cell || glyph.pixel((x - 2) as usize, (y - 3) as usize));
+        }
+        assert!(is_on(&opaque, 1, 3));
+        assert!(is_on(&opaque, (2 + advance) as usize, 3));
+        assert!(is_on(&opaque, 2, (3 + height) as usize));
+        assert!(!is_on(&opaque, 2, (2 + height) as usize));
+    }
+
+    #[test]
+    fn char_straddles_display_bands() {
+        let glyph = NX5.glyph('M').unwrap();
+        for y in 0..12 {
+            let mut canvas = canvas();
+            canvas.draw_char(3, y, 'M', &NX5, Pixel::On, None);
+            assert!(shows_glyph(&canvas, 3, y, &glyph));
+            let count = (0..glyph.height)
+                .flat_map(|dy| (0..glyph.width).map(move |dx| (dx, dy)))
+                .filter(|&(dx, dy)| glyph.pixel(dx, dy))
+                .count();
+            assert_eq!(lit(&canvas).len(), count);
+        }
+    }
+
+    #[test]
+    fn char_is_clipped_at_the_canvas_edge() {
+        let glyph = NX5.glyph('W').unwrap();
+        for &(x, y) in &[(-2, -3), (13, 13), (-2, 13), (13, -3), (-20, 4), (40, 4)] {
+            let mut canvas = canvas();
+            canvas.fill_rect(0, 0, 16, 16, Pixel::On);
+            let width = canvas.draw_char(x, y, 'W', &NX5, Pixel::On, Some(Pixel::Off));
+            assert_eq!(width, NX5.advance('W') as i32);
+            assert!(shows_glyph(&canvas, x, y, &glyph));
+        }
+    }
+
+    #[test]
+    fn text_starts_new_lines_at_x() {
+        let mut canvas = canvas();
+        let width = canvas.draw_text(1, 1, "AB\nC", &NX5, Pixel::On, None);
+        assert_eq!(width, NX5.advance('C') as i32);
+
+        let a = NX5.glyph('A').unwrap();
+        let b = NX5.glyph('B').unwrap();
+        let c = NX5.glyph('C').unwrap();
+        assert!(shows_glyph(&canvas, 1, 1, &a));
+        assert!(shows_glyph(&canvas, 1 + NX5.advance('A') as i32, 1, &b));
+        assert!(shows_glyph(&canvas, 1, 1 + NX5.line_spacing() as i32, &c));
+
+        // Nothing is drawn past the end of each line
+        let right = 1 + NX5.advance('A') + NX5.advance('B');
+        assert!(lit(&canvas).iter().all(|&(x, _)| x < right));
+    }
+}
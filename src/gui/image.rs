@@ -0,0 +1,809 @@
+///
+/// # Image loading
+///
+/// Decodes PBM/PGM, BMP and (with the `png` feature) PNG images to greyscale and
+/// converts them to a `Bitmap` or `MonochromeCanvas` using a threshold or
+/// dithering.
+///
+/// Dark pixels of an image are set in the bitmap, matching the logo. Use
+/// `Image::invert` to set the light pixels instead.
+///
+use super::bitmap::Bitmap;
+use super::display::MonochromeCanvas;
+use crate::Error;
+use alloc::format;
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+
+/// Ordered dithering thresholds (0-15)
+const BAYER: [[u8; 4]; 4] = [[0, 8, 2, 10], [12, 4, 14, 6], [3, 11, 1, 9], [15, 7, 13, 5]];
+
+/// Alpha at or above which a pixel is included in the mask
+const ALPHA_THRESHOLD: u8 = 128;
+
+/// Largest image (in pixels) that will be decoded, far larger than any display
+pub const MAX_PIXELS: usize = 4096 * 4096;
+
+///
+/// How greyscale is converted to monochrome
+///
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Dither {
+    /// Pixels darker than the level are set
+    Threshold(u8),
+
+    /// Ordered dithering with a 4x4 Bayer matrix, suits flat areas and animation
+    Bayer,
+
+    /// Floyd–Steinberg error diffusion, suits photos and artwork
+    FloydSteinberg,
+}
+
+impl Default for Dither {
+    fn default() -> Self {
+        Dither::Threshold(128)
+    }
+}
+
+///
+/// Greyscale image with optional transparency
+///
+/// Transparent pixels (alpha below 128) are left out of the mask of the bitmap
+/// so they are not drawn by `BlitMode::Masked`.
+///
+#[derive(Clone, Debug)]
+pub struct Image {
+    width: usize,
+    height: usize,
+    luma: Vec<u8>,
+    alpha: Option<Vec<u8>>,
+}
+
+impl Image {
+    ///
+    /// Create an image from luma (brightness) values, one byte per pixel
+    /// from the top left
+    ///
+    pub fn new(width: usize, height: usize, luma: Vec<u8>) -> Self {
+        if luma.len() != width * height {
+            panic!("Image data must be {} bytes long", width * height)
+        }
+
+        Image {
+            width,
+            height,
+            luma,
+            alpha: None,
+        }
+    }
+
+    ///
+    /// Set the alpha (opacity) of each pixel
+    ///
+    pub fn set_alpha(&mut self, alpha: Vec<u8>) {
+        if alpha.len() != self.luma.len() {
+            panic!("Image alpha must be {} bytes long", self.luma.len())
+        }
+        self.alpha = Some(alpha);
+    }
+
+    ///
+    /// Read an image from a file, see `decode` for supported formats
+    ///
+    #[cfg(feature = "std")]
+    pub fn open<P: AsRef<std::path::Path>>(path: P) -> Result<Self, Error> {
+        Self::decode(&std::fs::read(path)?)
+    }
+
+    ///
+    /// Decode an image, the format is identified from the data
+    ///
+    /// Supported formats are PBM/PGM (plain and raw), uncompressed BMP and PNG
+    /// (requires the `png` feature).
+    ///
+    pub fn decode(data: &[u8]) -> Result<Self, Error> {
+        match data {
+            [b'P', b'1' | b'2' | b'4' | b'5', ..] => Self::from_pbm(data),
+            [b'B', b'M', ..] => Self::from_bmp(data),
+            #[cfg(feature = "png")]
+            [0x89, b'P', b'N', b'G', ..] => Self::from_png(data),
+            _ => Err(invalid("unknown image format")),
+        }
+    }
+
+    ///
+    /// Decode a PBM (P1 or P4) or PGM (P2 or P5) image
+    ///
+    pub fn from_pbm(data: &[u8]) -> Result<Self, Error> {
+        let mut header = Tokens { data, pos: 2 };
+        let format = data.get(..2).unwrap_or_default();
+        let width = header.number()?;
+        let height = header.number()?;
+        let max = match format {
+            b"P2" | b"P5" => header.number()?.clamp(1, 65535),
+            _ => 1,
+        };
+        let size = pixel_count(width, height)?;
+
+        let luma = match format {
+            b"P1" => (0..size)
+                .map(|_| match header.pixel()? {
+                    b'0' => Ok(255),
+                    b'1' => Ok(0),
+                    _ => Err(invalid("invalid PBM pixel")),
+                })
+                .collect::<Result<Vec<u8>, Error>>()?,
+            b"P2" => (0..size)
+                .map(|_| Ok(scale(header.number()?, max)))
+                .collect::<Result<Vec<u8>, Error>>()?,
+            b"P4" => {
+                let stride = width.div_ceil(8);
+                let raster = header.raster(checked_mul(stride, height)?)?;
+                (0..size)
+                    .map(|idx| {
+                        let (x, y) = (idx % width, idx / width);
+                        let byte = raster[y * stride + (x >> 3)];
+                        if byte & (0x80 >> (x & 7)) != 0 {
+                            0
+                        } else {
+                            255
+                        }
+                    })
+                    .collect()
+            }
+            b"P5" => {
+                let bytes = if max > 255 { 2 } else { 1 };
+                let raster = header.raster(checked_mul(size, bytes)?)?;
+                raster
+                    .chunks_exact(bytes)
+                    .map(|sample| match sample {
+                        [high, low] => scale(u16::from_be_bytes([*high, *low]) as usize, max),
+                        [value] => scale(*value as usize, max),
+                        _ => unreachable!(),
+                    })
+                    .collect()
+            }
+            _ => return Err(invalid("not a PBM or PGM image")),
+        };
+
+        Ok(Self::new(width, height, luma))
+    }
+
+    ///
+    /// Decode an uncompressed BMP image
+    ///
+    /// 1, 4 and 8 bit paletted images and 16, 24 and 32 bit images (including
+    /// bit field masks with alpha) are supported.
+    ///
+    pub fn from_bmp(data: &[u8]) -> Result<Self, Error> {
+        let u16_at = |offset: usize| -> Result<u16, Error> {
+            data.get(offset..offset + 2)
+                .map(|b| u16::from_le_bytes([b[0], b[1]]))
+                .ok_or_else(|| invalid("truncated BMP header"))
+        };
+        let u32_at = |offset: usize| -> Result<u32, Error> {
+            data.get(offset..offset + 4)
+                .map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+                .ok_or_else(|| invalid("truncated BMP header"))
+        };
+
+        if data.get(..2) != Some(b"BM") {
+            return Err(invalid("not a BMP image"));
+        }
+        let offset = u32_at(10)? as usize;
+        let header_size = u32_at(14)? as usize;
+        if header_size < 40 {
+            return Err(invalid("unsupported BMP header"));
+        }
+        let width = u32_at(18)? as i32;
+        let height = u32_at(22)? as i32;
+        let bpp = u16_at(28)? as usize;
+        let compression = u32_at(30)?;
+        let colours = u32_at(46)? as usize;
+        if width <= 0 || height == 0 {
+            return Err(invalid("invalid BMP size"));
+        }
+        let (width, top_down) = (width as usize, height < 0);
+        let height = height.unsigned_abs() as usize;
+        let size = pixel_count(width, height)?;
+
+        // Bit field masks (red, green, blue, alpha) for 16 and 32 bit images
+        let masks = match (compression, bpp) {
+            (0, 16) => [0x7C00, 0x03E0, 0x001F, 0],
+            (0, 32) => [0x00FF_0000, 0x0000_FF00, 0x0000_00FF, 0],
+            (3, 16 | 32) => [
+                u32_at(54)?,
+                u32_at(58)?,
+                u32_at(62)?,
+                if header_size >= 56 { u32_at(66)? } else { 0 },
+            ],
+            (0, 1 | 4 | 8 | 24) => [0; 4],
+            (0, _) => return Err(invalid(&format!("unsupported BMP bit depth {}", bpp))),
+            _ => return Err(invalid("compressed BMP images are not supported")),
+        };
+
+        let palette: Vec<u8> = if bpp <= 8 {
+            let count = if colours == 0 { 1 << bpp } else { colours };
+            let start = header_size.checked_add(14).ok_or_else(too_large)?;
+            let end = checked_mul(count, 4)?
+                .checked_add(start)
+                .ok_or_else(too_large)?;
+            let table = data
+                .get(start..end)
+                .ok_or_else(|| invalid("truncated BMP palette"))?;
+            table
+                .chunks_exact(4)
+                .map(|bgr| to_luma(bgr[2], bgr[1], bgr[0]))
+                .collect()
+        } else {
+            Vec::new()
+        };
+
+        let stride = checked_mul(bpp, width)?.div_ceil(32) * 4;
+        let end = checked_mul(stride, height)?
+            .checked_add(offset)
+            .ok_or_else(too_large)?;
+        let pixels = data
+            .get(offset..end)
+            .ok_or_else(|| invalid("truncated BMP pixel data"))?;
+
+        let mut luma = Vec::with_capacity(size);
+        let mut alpha = Vec::with_capacity(size);
+        for y in 0..height {
+            let row = if top_down { y } else { height - 1 - y };
+            let row = &pixels[row * stride..(row + 1) * stride];
+            for x in 0..width {
+                let (value, opacity) = match bpp {
+                    1 | 4 | 8 => {
+                        let bit = x * bpp;
+                        let index = (row[bit >> 3] >> (8 - bpp - (bit & 7))) & (0xFF >> (8 - bpp));
+                        let value = *palette
+                            .get(index as usize)
+                            .ok_or_else(|| invalid("BMP palette index out of range"))?;
+                        (value, 255)
+                    }
+                    24 => (to_luma(row[x * 3 + 2], row[x * 3 + 1], row[x * 3]), 255),
+                    _ => {
+                        let bytes = bpp / 8;
+                        let pixel = row[x * bytes..(x + 1) * bytes]
+                            .iter()
+                            .rev()
+                            .fold(0u32, |pixel, byte| (pixel << 8) | *byte as u32);
+                        let [red, green, blue, opacity] = masks.map(|mask| channel(pixel, mask));
+                        let opacity = if masks[3] == 0 { 255 } else { opacity };
+                        (to_luma(red, green, blue), opacity)
+                    }
+                };
+                luma.push(value);
+                alpha.push(opacity);
+            }
+        }
+
+        let mut image = Self::new(width, height, luma);
+        if masks[3] != 0 {
+            image.set_alpha(alpha);
+        }
+        Ok(image)
+    }
+
+    ///
+    /// Decode a PNG image
+    ///
+    #[cfg(feature = "png")]
+    pub fn from_png(data: &[u8]) -> Result<Self, Error> {
+        use png::ColorType;
+
+        let mut decoder = png::Decoder::new(std::io::Cursor::new(data));
+        decoder.set_transformations(png::Transformations::normalize_to_color8());
+        let mut reader = decoder.read_info()?;
+        let size = reader
+            .output_buffer_size()
+            .ok_or_else(|| invalid("PNG image is too large"))?;
+        let mut buffer = vec![0; size];
+        let info = reader.next_frame(&mut buffer)?;
+
+        let (width, height) = (info.width as usize, info.height as usize);
+        let size = pixel_count(width, height)?;
+        let samples = info.color_type.samples();
+        let mut luma = Vec::with_capacity(size);
+        let mut alpha = Vec::with_capacity(size);
+        for row in buffer.chunks(info.line_size).take(height) {
+            for pixel in row.chunks_exact(samples).take(width) {
+                let (value, opacity) = match (info.color_type, pixel) {
+                    (ColorType::GrayscaleAlpha, [value, opacity]) => (*value, *opacity),
+                    (ColorType::Rgb, [red, green, blue]) => (to_luma(*red, *green, *blue), 255),
+                    (ColorType::Rgba, [red, green, blue, opacity]) => {
+                        (to_luma(*red, *green, *blue), *opacity)
+                    }
+                    (_, [value, ..]) => (*value, 255),
+                    _ => return Err(invalid("unsupported PNG colour type")),
+                };
+                luma.push(value);
+                alpha.push(opacity);
+            }
+        }
+
+        let mut image = Self::new(width, height, luma);
+        if matches!(info.color_type, ColorType::GrayscaleAlpha | ColorType::Rgba) {
+            image.set_alpha(alpha);
+        }
+        Ok(image)
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    ///
+    /// Luma (brightness) of a pixel, 0 is black
+    ///
+    pub fn luma(&self, x: usize, y: usize) -> u8 {
+        self.luma[y * self.width + x]
+    }
+
+    ///
+    /// Alpha (opacity) of a pixel, 255 is opaque
+    ///
+    pub fn alpha(&self, x: usize, y: usize) -> u8 {
+        self.alpha
+            .as_ref()
+            .map_or(255, |alpha| alpha[y * self.width + x])
+    }
+
+    ///
+    /// Swap light and dark
+    ///
+    pub fn invert(&mut self) {
+        for value in self.luma.iter_mut() {
+            *value = !*value;
+        }
+    }
+
+    ///
+    /// Scale the image to a new size, each pixel is the average of the pixels it
+    /// covers in the original image
+    ///
+    pub fn resize(&self, width: usize, height: usize) -> Image {
+        let average = |values: &[u8], x: usize, y: usize| -> u8 {
+            let (x0, y0) = (x * self.width / width, y * self.height / height);
+            let x1 = ((x + 1) * self.width / width).max(x0 + 1);
+            let y1 = ((y + 1) * self.height / height).max(y0 + 1);
+            let total: usize = (y0..y1)
+                .flat_map(|sy| (x0..x1).map(move |sx| values[sy * self.width + sx] as usize))
+                .sum();
+            (total / ((x1 - x0) * (y1 - y0))) as u8
+        };
+        let resample = |values: &[u8]| -> Vec<u8> {
+            (0..width * height)
+                .map(|idx| average(values, idx % width, idx / width))
+                .collect()
+        };
+
+        let mut image = Image::new(width, height, resample(&self.luma));
+        if let Some(alpha) = &self.alpha {
+            image.set_alpha(resample(alpha));
+        }
+        image
+    }
+
+    ///
+    /// Convert to a monochrome bitmap, with a mask if the image has transparency
+    ///
+    pub fn to_bitmap(&self, dither: Dither) -> Bitmap<'static> {
+        let (width, height) = (self.width, self.height);
+        let mut data = vec![0u8; Bitmap::data_size(width, height)];
+        let mut set = |x: usize, y: usize| data[(y >> 3) * width + x] |= 1 << (y & 7);
+
+        match dither {
+            Dither::Threshold(level) => {
+                for y in 0..height {
+                    for x in 0..width {
+                        if self.luma(x, y) < level {
+                            set(x, y);
+                        }
+                    }
+                }
+            }
+            Dither::Bayer => {
+                for y in 0..height {
+                    for x in 0..width {
+                        if self.luma(x, y) < BAYER[y & 3][x & 3] * 16 + 8 {
+                            set(x, y);
+                        }
+                    }
+                }
+            }
+            Dither::FloydSteinberg => {
+                // Errors carried to the current and next rows, offset by one
+                // so the pixels either side of the image can be written
+                let mut current = vec![0i32; width + 2];
+                let mut next = vec![0i32; width + 2];
+                for y in 0..height {
+                    for x in 0..width {
+                        let value = self.luma(x, y) as i32 + current[x + 1] / 16;
+                        let error = if value < 128 {
+                            set(x, y);
+                            value
+                        } else {
+                            value - 255
+                        };
+                        current[x + 2] += error * 7;
+                        next[x] += error * 3;
+                        next[x + 1] += error * 5;
+                        next[x + 2] += error;
+                    }
+                    core::mem::swap(&mut current, &mut next);
+                    next.iter_mut().for_each(|error| *error = 0);
+                }
+            }
+        }
+
+        let mut bitmap = Bitmap::new(width, height, data);
+        if let Some(alpha) = &self.alpha {
+            let mut mask = vec![0u8; Bitmap::data_size(width, height)];
+            for (idx, _) in alpha
+                .iter()
+                .enumerate()
+                .filter(|(_, alpha)| **alpha >= ALPHA_THRESHOLD)
+            {
+                let (x, y) = (idx % width, idx / width);
+                mask[(y >> 3) * width + x] |= 1 << (y & 7);
+            }
+            bitmap.set_mask(mask);
+        }
+        bitmap
+    }
+
+    ///
+    /// Convert to a canvas, the height is rounded up to a multiple of 8
+    ///
+    pub fn to_canvas(&self, dither: Dither) -> MonochromeCanvas {
+        let bitmap = self.to_bitmap(dither);
+        MonochromeCanvas::from_buffer(self.width, self.height.div_ceil(8) * 8, bitmap.data())
+    }
+}
+
+/// Header and raster of a PBM/PGM image
+struct Tokens<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Tokens<'a> {
+    /// Skip whitespace and comments
+    fn skip_space(&mut self) {
+        while let Some(byte) = self.data.get(self.pos) {
+            match byte {
+                b'#' => {
+                    while self.data.get(self.pos).is_some_and(|byte| *byte != b'\n') {
+                        self.pos += 1;
+                    }
+                }
+                byte if byte.is_ascii_whitespace() => self.pos += 1,
+                _ => break,
+            }
+        }
+    }
+
+    /// Decimal number
+    fn number(&mut self) -> Result<usize, Error> {
+        self.skip_space();
+        let start = self.pos;
+        while self.data.get(self.pos).is_some_and(u8::is_ascii_digit) {
+            self.pos += 1;
+        }
+        core::str::from_utf8(&self.data[start..self.pos])
+            .ok()
+            .and_then(|digits| digits.parse().ok())
+            .ok_or_else(|| invalid("invalid PBM header"))
+    }
+
+    /// Single character pixel of a plain PBM
+    fn pixel(&mut self) -> Result<u8, Error> {
+        self.skip_space();
+        let byte = self
+            .data
+            .get(self.pos)
+            .ok_or_else(|| invalid("truncated PBM data"))?;
+        self.pos += 1;
+        Ok(*byte)
+    }
+
+    /// Binary raster following the single whitespace character after the header
+    fn raster(&mut self, size: usize) -> Result<&'a [u8], Error> {
+        let start = self.pos + 1;
+        let end = start.checked_add(size).ok_or_else(too_large)?;
+        self.data
+            .get(start..end)
+            .ok_or_else(|| invalid("truncated PBM data"))
+    }
+}
+
+fn invalid(message: &str) -> Error {
+    Error::InvalidImage(String::from(message))
+}
+
+fn too_large() -> Error {
+    invalid("image is too large")
+}
+
+fn checked_mul(a: usize, b: usize) -> Result<usize, Error> {
+    a.checked_mul(b).ok_or_else(too_large)
+}
+
+/// Number of pixels in an image, images larger than `MAX_PIXELS` are rejected
+fn pixel_count(width: usize, height: usize) -> Result<usize, Error> {
+    match width.checked_mul(height) {
+        Some(size) if size <= MAX_PIXELS => Ok(size),
+        _ => Err(too_large()),
+    }
+}
+
+/// Scale a sample with a maximum value to 0-255
+fn scale(value: usize, max: usize) -> u8 {
+    (value.min(max) * 255 / max) as u8
+}
+
+/// Perceived brightness of a colour
+fn to_luma(red: u8, green: u8, blue: u8) -> u8 {
+    ((red as u32 * 77 + green as u32 * 150 + blue as u32 * 29) >> 8) as u8
+}
+
+/// Extract a colour channel described by a bit field mask, scaled to 0-255
+fn channel(pixel: u32, mask: u32) -> u8 {
+    if mask == 0 {
+        return 0;
+    }
+    let shift = mask.trailing_zeros();
+    let max = (mask >> shift) as u64;
+    (((pixel & mask) >> shift) as u64 * 255 / max) as u8
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gui::display::Canvas;
+
+    /// BMP file with a header of `header_size` bytes, `table` (palette or bit
+    /// field masks) follows the header and then the pixel rows
+    fn bmp(header_size: usize, width: i32, height: i32, bpp: u16, table: &[u8]) -> Vec<u8> {
+        let mut data = vec![0u8; 14 + header_size];
+        data[..2].copy_from_slice(b"BM");
+        data[14..18].copy_from_slice(&(header_size as u32).to_le_bytes());
+        data[18..22].copy_from_slice(&width.to_le_bytes());
+        data[22..26].copy_from_slice(&height.to_le_bytes());
+        data[26..28].copy_from_slice(&1u16.to_le_bytes());
+        data[28..30].copy_from_slice(&bpp.to_le_bytes());
+        data.extend_from_slice(table);
+        let offset = data.len() as u32;
+        data[10..14].copy_from_slice(&offset.to_le_bytes());
+        data
+    }
+
+    fn set_compression(data: &mut [u8], compression: u32) {
+        data[30..34].copy_from_slice(&compression.to_le_bytes());
+    }
+
+    fn luma(image: &Image) -> Vec<u8> {
+        (0..image.height())
+            .flat_map(|y| (0..image.width()).map(move |x| image.luma(x, y)))
+            .collect()
+    }
+
+    fn set_pixels(bitmap: &Bitmap) -> usize {
+        (0..bitmap.height())
+            .flat_map(|y| (0..bitmap.width()).map(move |x| (x, y)))
+            .filter(|&(x, y)| bitmap.pixel(x, y))
+            .count()
+    }
+
+    #[test]
+    fn decode_plain_pbm_and_pgm() {
+        let image = Image::decode(b"P1\n# comment\n3 2\n1 0 1\n0 1 0\n").unwrap();
+        assert_eq!((image.width(), image.height()), (3, 2));
+        assert_eq!(luma(&image), [0, 255, 0, 255, 0, 255]);
+
+        let image = Image::decode(b"P2 2 2 4\n0 1\n2 4\n").unwrap();
+        assert_eq!(luma(&image), [0, 63, 127, 255]);
+
+        assert!(Image::decode(b"P1 2 2\n1 0 1").is_err());
+        assert!(Image::decode(b"P1 1 1\n2").is_err());
+    }
+
+    #[test]
+    fn decode_raw_pbm_and_pgm() {
+        let image = Image::decode(b"P4\n10 2\n\xA0\x40\x00\xC0").unwrap();
+        assert_eq!(
+            luma(&image),
+            [
+                0, 255, 0, 255, 255, 255, 255, 255, 255, 0, 255, 255, 255, 255, 255, 255, 255, 255,
+                0, 0
+            ]
+        );
+
+        let image = Image::decode(b"P5 3 1 255\n\x00\x80\xFF").unwrap();
+        assert_eq!(luma(&image), [0, 128, 255]);
+
+        let image = Image::decode(b"P5 2 1 65535\n\x00\x00\xFF\xFF").unwrap();
+        assert_eq!(luma(&image), [0, 255]);
+
+        assert!(Image::decode(b"P5 2 2 255\n\x00").is_err());
+    }
+
+    #[test]
+    fn reject_images_that_are_too_large() {
+        assert!(matches!(
+            Image::decode(b"P4 99999999999 99999999999\n"),
+            Err(Error::InvalidImage(_))
+        ));
+        assert!(matches!(
+            Image::decode(b"P5 5000 5000 255\n"),
+            Err(Error::InvalidImage(_))
+        ));
+
+        let data = bmp(40, 0x7FFF_FFFF, -0x7FFF_FFFF, 24, &[]);
+        assert!(matches!(Image::decode(&data), Err(Error::InvalidImage(_))));
+
+        // Palette larger than the address space
+        let mut data = bmp(40, 1, 1, 8, &[]);
+        data[46..50].copy_from_slice(&u32::MAX.to_le_bytes());
+        assert!(matches!(Image::decode(&data), Err(Error::InvalidImage(_))));
+    }
+
+    #[test]
+    fn decode_paletted_bmp() {
+        // Black and white palette, rows bottom up
+        let mut data = bmp(40, 3, 2, 1, &[0, 0, 0, 0, 255, 255, 255, 0]);
+        data.extend_from_slice(&[0b1010_0000, 0, 0, 0]);
+        data.extend_from_slice(&[0b0100_0000, 0, 0, 0]);
+        let image = Image::decode(&data).unwrap();
+        assert_eq!(luma(&image), [0, 255, 0, 255, 0, 255]);
+
+        // 8 bit with a two colour palette, top down
+        let mut data = bmp(40, 2, -1, 8, &[0, 0, 0, 0, 255, 255, 255, 0]);
+        data[46..50].copy_from_slice(&2u32.to_le_bytes());
+        data.extend_from_slice(&[1, 0, 0, 0]);
+        let image = Image::decode(&data).unwrap();
+        assert_eq!(luma(&image), [255, 0]);
+
+        // Index past the end of the palette
+        let mut data = bmp(40, 1, 1, 8, &[0, 0, 0, 0]);
+        data[46..50].copy_from_slice(&1u32.to_le_bytes());
+        data.extend_from_slice(&[5, 0, 0, 0]);
+        assert!(Image::decode(&data).is_err());
+    }
+
+    #[test]
+    fn decode_true_colour_bmp() {
+        let mut data = bmp(40, 2, 1, 24, &[]);
+        data.extend_from_slice(&[255, 255, 255, 0, 0, 0, 0, 0]);
+        let image = Image::decode(&data).unwrap();
+        assert_eq!(luma(&image), [255, 0]);
+
+        // 16 bit 5-5-5
+        let mut data = bmp(40, 2, 1, 16, &[]);
+        data.extend_from_slice(&[0xFF, 0x7F, 0x00, 0x00]);
+        let image = Image::decode(&data).unwrap();
+        assert_eq!(luma(&image), [255, 0]);
+
+        // Truncated pixel data
+        let mut data = bmp(40, 2, 2, 24, &[]);
+        data.extend_from_slice(&[0; 8]);
+        assert!(Image::decode(&data).is_err());
+    }
+
+    #[test]
+    fn decode_bmp_bit_fields_with_alpha() {
+        // Red, green, blue and alpha masks in a V4 header
+        let mut data = bmp(108, 2, 1, 32, &[]);
+        set_compression(&mut data, 3);
+        for (idx, mask) in [0x00FF_0000u32, 0x0000_FF00, 0x0000_00FF, 0xFF00_0000]
+            .iter()
+            .enumerate()
+        {
+            data[54 + idx * 4..58 + idx * 4].copy_from_slice(&mask.to_le_bytes());
+        }
+        data.extend_from_slice(&[255, 255, 255, 255, 0, 0, 0, 0]);
+        let image = Image::decode(&data).unwrap();
+        assert_eq!(luma(&image), [255, 0]);
+        assert_eq!((image.alpha(0, 0), image.alpha(1, 0)), (255, 0));
+
+        let bitmap = image.to_bitmap(Dither::default());
+        assert_eq!(bitmap.mask().map(|mask| mask[0] & 0x03), Some(0x01));
+    }
+
+    #[test]
+    fn full_width_bit_field_mask() {
+        assert_eq!(channel(u32::MAX, u32::MAX), 255);
+        assert_eq!(channel(0x8000_0000, u32::MAX), 127);
+        assert_eq!(channel(0x0000_0F00, 0x0000_0F00), 255);
+        assert_eq!(channel(0x1234, 0), 0);
+
+        let mut data = bmp(40, 1, 1, 32, &[]);
+        set_compression(&mut data, 3);
+        data.extend_from_slice(&u32::MAX.to_le_bytes());
+        data.extend_from_slice(&u32::MAX.to_le_bytes());
+        data.extend_from_slice(&u32::MAX.to_le_bytes());
+        // Offset points past the masks
+        let offset = data.len() as u32;
+        data[10..14].copy_from_slice(&offset.to_le_bytes());
+        data.extend_from_slice(&[0xFF; 4]);
+        let image = Image::decode(&data).unwrap();
+        assert_eq!(luma(&image), [255]);
+    }
+
+    #[cfg(feature = "png")]
+    #[test]
+    fn decode_png() {
+        let mut data = Vec::new();
+        let mut encoder = png::Encoder::new(&mut data, 2, 1);
+        encoder.set_color(png::ColorType::GrayscaleAlpha);
+        encoder.set_depth(png::BitDepth::Eight);
+        let mut writer = encoder.write_header().unwrap();
+        writer.write_image_data(&[0, 255, 255, 0]).unwrap();
+        writer.finish().unwrap();
+
+        let image = Image::decode(&data).unwrap();
+        assert_eq!((image.width(), image.height()), (2, 1));
+        assert_eq!(luma(&image), [0, 255]);
+        assert_eq!((image.alpha(0, 0), image.alpha(1, 0)), (255, 0));
+    }
+
+    #[test]
+    fn unknown_format() {
+        assert!(matches!(
+            Image::decode(b"GIF89a"),
+            Err(Error::InvalidImage(_))
+        ));
+    }
+
+    #[test]
+    fn threshold_dither() {
+        let image = Image::new(4, 1, vec![0, 100, 150, 255]);
+        let bitmap = image.to_bitmap(Dither::Threshold(128));
+        assert_eq!(bitmap.data(), &[1, 1, 0, 0]);
+        assert_eq!(
+            image.to_bitmap(Dither::Threshold(200)).data(),
+            &[1, 1, 1, 0]
+        );
+        assert!(bitmap.mask().is_none());
+    }
+
+    #[test]
+    fn bayer_dither() {
+        let grey = Image::new(8, 8, vec![128; 64]);
+        assert_eq!(set_pixels(&grey.to_bitmap(Dither::Bayer)), 32);
+
+        let black = Image::new(8, 8, vec![0; 64]);
+        assert_eq!(set_pixels(&black.to_bitmap(Dither::Bayer)), 64);
+        let white = Image::new(8, 8, vec![255; 64]);
+        assert_eq!(set_pixels(&white.to_bitmap(Dither::Bayer)), 0);
+    }
+
+    #[test]
+    fn floyd_steinberg_dither() {
+        let grey = Image::new(16, 16, vec![128; 256]);
+        let set = set_pixels(&grey.to_bitmap(Dither::FloydSteinberg));
+        assert!((120..=136).contains(&set), "{} pixels set", set);
+
+        let black = Image::new(8, 8, vec![0; 64]);
+        assert_eq!(set_pixels(&black.to_bitmap(Dither::FloydSteinberg)), 64);
+        let white = Image::new(8, 8, vec![255; 64]);
+        assert_eq!(set_pixels(&white.to_bitmap(Dither::FloydSteinberg)), 0);
+    }
+
+    #[test]
+    fn resize_and_convert_to_canvas() {
+        let image = Image::new(4, 2, vec![0, 0, 255, 255, 0, 0, 255, 255]);
+        let small = image.resize(2, 1);
+        assert_eq!(luma(&small), [0, 255]);
+
+        let canvas = image.to_canvas(Dither::default());
+        assert_eq!((canvas.width(), canvas.height()), (4, 8));
+    }
+}
@@ -1,4 +1,7 @@
-#[allow(unused)]
+/// Size of the logo bitmap in pixels
+pub const LOGO_WIDTH: usize = 128;
+pub const LOGO_HEIGHT: usize = 64;
+
 pub const LOGO: [u8; 1024] = [
     0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
     0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
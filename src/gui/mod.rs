@@ -1,4 +1,9 @@
+pub mod bitmap;
 pub mod display;
 pub mod font;
+pub mod graphics;
+pub mod image;
 pub mod logo;
+pub mod text;
 pub mod ui;
+pub mod viewport;
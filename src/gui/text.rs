@@ -0,0 +1,321 @@
+///
+/// # Text layout
+///
+/// Measuring, aligning, truncating, wrapping and styling text drawn with a
+/// proportional `Font`.
+///
+use super::font::Font;
+use alloc::borrow::Cow;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+/// Appended to text that has been truncated
+pub const ELLIPSIS: &str = "\u{2026}";
+
+/// Appended to truncated text when the font has no ellipsis character
+pub const ELLIPSIS_FALLBACK: &str = "...";
+
+///
+/// Ellipsis used to truncate text in a font, the `ELLIPSIS` character if the
+/// font has a glyph for it otherwise `ELLIPSIS_FALLBACK`
+///
+pub fn ellipsis(font: &dyn Font) -> &'static str {
+    if ELLIPSIS.chars().all(|c| font.glyph(c).is_some()) {
+        ELLIPSIS
+    } else {
+        ELLIPSIS_FALLBACK
+    }
+}
+
+///
+/// Horizontal alignment of text within a box
+///
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum Align {
+    #[default]
+    Left,
+    Centre,
+    Right,
+}
+
+impl Align {
+    ///
+    /// Offset from the left of a box of `box_width` pixels to start text that is
+    /// `text_width` pixels wide, text wider than the box starts at the left
+    ///
+    pub fn offset(&self, text_width: usize, box_width: usize) -> usize {
+        let space = box_width.saturating_sub(text_width);
+        match self {
+            Align::Left => 0,
+            Align::Centre => space / 2,
+            Align::Right => space,
+        }
+    }
+}
+
+///
+/// Width of text in pixels, the widest line if the text contains newlines
+///
+/// Spacing after the last character of a line is not included.
+///
+pub fn measure(s: &str, font: &dyn Font) -> usize {
+    s.split('\n')
+        .map(|line| line_width(line, font))
+        .max()
+        .unwrap_or(0)
+}
+
+/// Width of a single line excluding the spacing after the last character
+fn line_width(line: &str, font: &dyn Font) -> usize {
+    line.chars()
+        .map(|c| font.advance(c))
+        .sum::<usize>()
+        .saturating_sub(1)
+}
+
+///
+/// Truncate a single line of text to fit within `width` pixels
+///
+/// Text that does not fit is cut at a character boundary and an ellipsis (see
+/// `ellipsis`) appended, text that already fits is returned unchanged. If not even the
+/// ellipsis fits the result is empty.
+///
+pub fn truncate<'a>(s: &'a str, font: &dyn Font, width: usize) -> Cow<'a, str> {
+    if measure(s, font) <= width {
+        return Cow::Borrowed(s);
+    }
+
+    let suffix = ellipsis(font);
+    let ellipsis = line_width(suffix, font) + 1;
+    if ellipsis > width + 1 {
+        return Cow::Borrowed("");
+    }
+
+    let mut used = 0;
+    let mut end = 0;
+    for (idx, c) in s.char_indices() {
+        let advance = font.advance(c);
+        if used + advance + ellipsis > width + 1 {
+            break;
+        }
+        used += advance;
+        end = idx + c.len_utf8();
+    }
+
+    let mut truncated = String::from(s[..end].trim_end());
+    truncated.push_str(suffix);
+    Cow::Owned(truncated)
+}
+
+///
+/// Inline style of a character
+///
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct Style {
+    /// Drawn twice, one pixel apart
+    pub bold: bool,
+
+    /// Line drawn below the text
+    pub underline: bool,
+
+    /// Text and background are swapped
+    pub inverse: bool,
+}
+
+impl Style {
+    ///
+    /// Horizontal space taken by a character in this style including spacing
+    ///
+    pub fn advance(&self, c: char, font: &dyn Font) -> usize {
+        match font.advance(c) {
+            0 => 0,
+            advance if self.bold => advance + 1,
+            advance => advance,
+        }
+    }
+}
+
+///
+/// Parse text with inline style markup into styled characters
+///
+/// `*` toggles bold, `_` toggles underline and `~` toggles inverse, a `\`
+/// includes the following character as is (e.g. `\*`).
+///
+pub fn parse_markup(s: &str) -> Vec<(char, Style)> {
+    let mut style = Style::default();
+    let mut styled = Vec::with_capacity(s.len());
+    let mut chars = s.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '*' => style.bold = !style.bold,
+            '_' => style.underline = !style.underline,
+            '~' => style.inverse = !style.inverse,
+            '\\' => {
+                if let Some(c) = chars.next() {
+                    styled.push((c, style));
+                }
+            }
+            _ => styled.push((c, style)),
+        }
+    }
+    styled
+}
+
+///
+/// Width of a line of styled text in pixels, excluding the spacing after the
+/// last character
+///
+pub fn measure_styled(line: &[(char, Style)], font: &dyn Font) -> usize {
+    line.iter()
+        .map(|&(c, style)| style.advance(c, font))
+        .sum::<usize>()
+        .saturating_sub(1)
+}
+
+///
+/// Word wrap a line of styled text to fit within `width` pixels
+///
+/// Lines are broken at a space where possible, words too long for a line are
+/// broken between characters. Spaces at a break are dropped.
+///
+pub fn wrap<'a>(
+    line: &'a [(char, Style)],
+    font: &dyn Font,
+    width: usize,
+) -> Vec<&'a [(char, Style)]> {
+    let mut lines = Vec::new();
+    let mut rest = line;
+    loop {
+        let mut used = 0;
+        let mut end = rest.len();
+        let mut space = None;
+        for (idx, &(c, style)) in rest.iter().enumerate() {
+            if c == ' ' {
+                space = Some(idx);
+            }
+            let advance = style.advance(c, font);
+            // Always take one character so the line makes progress
+            if idx > 0 && used + advance > width + 1 {
+                end = idx;
+                break;
+            }
+            used += advance;
+        }
+        if end == rest.len() {
+            lines.push(rest);
+            break;
+        }
+
+        let split = match space {
+            Some(space) if space > 0 => space,
+            _ => end,
+        };
+        lines.push(&rest[..split]);
+        rest = &rest[split..];
+        while let Some(((' ', _), tail)) = rest.split_first() {
+            rest = tail;
+        }
+        if rest.is_empty() {
+            break;
+        }
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gui::font::{GlyphRange, TableFont, NX5};
+    use alloc::string::ToString;
+    use alloc::vec;
+
+    /// Font with only ASCII characters (no ellipsis), each a 3 pixel wide block
+    static ASCII_GLYPHS: [(u8, &[u8]); 96] = [(3, &[0x1F, 0x1F, 0x1F]); 96];
+    static ASCII_RANGES: [GlyphRange; 1] = [GlyphRange::new(' ', 0, 0, &ASCII_GLYPHS)];
+    static ASCII_REPLACEMENT: (u8, &[u8]) = (1, &[0x1F]);
+    const ASCII: TableFont = TableFont::new(&ASCII_RANGES, &ASCII_REPLACEMENT, 5, 5, 8);
+
+    fn text(line: &[(char, Style)]) -> String {
+        line.iter().map(|&(c, _)| c).collect()
+    }
+
+    #[test]
+    fn measure_text() {
+        assert_eq!(measure("", &NX5), 0);
+        let ab = NX5.advance('A') + NX5.advance('B') - 1;
+        assert_eq!(measure("AB", &NX5), ab);
+        assert_eq!(measure("A\nAB\nB", &NX5), ab);
+    }
+
+    #[test]
+    fn align_offset() {
+        assert_eq!(Align::Left.offset(10, 30), 0);
+        assert_eq!(Align::Centre.offset(10, 30), 10);
+        assert_eq!(Align::Right.offset(10, 30), 20);
+        assert_eq!(Align::Right.offset(40, 30), 0);
+    }
+
+    #[test]
+    fn truncate_with_ellipsis_character() {
+        assert_eq!(ellipsis(&NX5), "\u{2026}");
+        assert!(matches!(
+            truncate("SHORT", &NX5, 100),
+            Cow::Borrowed("SHORT")
+        ));
+
+        let width = measure("HELLO", &NX5);
+        let truncated = truncate("HELLO WORLD", &NX5, width);
+        assert!(truncated.ends_with('\u{2026}'));
+        assert!(measure(&truncated, &NX5) <= width);
+        assert_eq!(truncate("HELLO WORLD", &NX5, 1), "");
+    }
+
+    #[test]
+    fn truncate_falls_back_to_dots() {
+        assert_eq!(ellipsis(&ASCII), "...");
+
+        let width = measure("HELLO", &ASCII);
+        let truncated = truncate("HELLO WORLD", &ASCII, width);
+        assert!(truncated.ends_with("..."));
+        assert!(measure(&truncated, &ASCII) <= width);
+    }
+
+    #[test]
+    fn parse_markup_styles() {
+        let bold = Style {
+            bold: true,
+            ..Style::default()
+        };
+        let inverse = Style {
+            inverse: true,
+            ..Style::default()
+        };
+        assert_eq!(
+            parse_markup("a*b*~c~\\*"),
+            vec![
+                ('a', Style::default()),
+                ('b', bold),
+                ('c', inverse),
+                ('*', Style::default()),
+            ]
+        );
+        assert!(bold.advance('A', &NX5) == NX5.advance('A') + 1);
+    }
+
+    #[test]
+    fn wrap_at_spaces_and_long_words() {
+        let line = parse_markup("HELLO BIG WORLD");
+        let width = measure("HELLO BIG", &NX5);
+        let lines: Vec<String> = wrap(&line, &NX5, width).iter().map(|l| text(l)).collect();
+        assert_eq!(lines, vec!["HELLO BIG", "WORLD"]);
+
+        let line = parse_markup("ABCDEFGH");
+        let width = measure("ABCD", &NX5);
+        let lines: Vec<String> = wrap(&line, &NX5, width).iter().map(|l| text(l)).collect();
+        assert_eq!(lines, vec!["ABCD", "EFGH"]);
+
+        let empty = parse_markup("");
+        assert_eq!(wrap(&empty, &NX5, 10).len(), 1);
+        assert_eq!("x".to_string(), text(&parse_markup("x")));
+    }
+}
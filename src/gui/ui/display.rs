@@ -1,3 +1,5 @@
+#![allow(dead_code)]
+
 struct Control {
     height: usize,
     width: usize,
@@ -1,6 +1,10 @@
 use crate::events::{Direction, Event};
-use crate::gui::display::{Canvas, MonochromeCanvas, Pixel};
+use crate::gui::display::{Canvas, Pixel};
+use crate::gui::font::{Font, NX5};
 use crate::gui::ui::{Control, EventHandler};
+use crate::gui::viewport::Viewport;
+use alloc::string::String;
+use alloc::vec::Vec;
 
 pub struct ListPanel<T> {
     dirty: bool,
@@ -8,6 +12,7 @@ pub struct ListPanel<T> {
     current: usize,
     offset: usize,
     control_size: (usize, usize),
+    font: &'static dyn Font,
 }
 
 impl<T> ListPanel<T> {
@@ -18,9 +23,18 @@ impl<T> ListPanel<T> {
             current: 0,
             offset: 0,
             control_size: (0, 0),
+            font: &NX5,
         }
     }
 
+    ///
+    /// Set the font used for items
+    ///
+    pub fn set_font(&mut self, font: &'static dyn Font) {
+        self.font = font;
+        self.dirty = true;
+    }
+
     pub fn add_item(&mut self, key: T, text: &str) {
         self.list.push((key, String::from(text)));
     }
@@ -43,10 +57,16 @@ impl<T> ListPanel<T> {
     }
 }
 
+impl<T> Default for ListPanel<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl<T> EventHandler for ListPanel<T> {
     fn handle(&mut self, event: &Event) -> bool {
-        match event {
-            Event::EncoderChange(_, direction, _) => match direction {
+        if let Event::EncoderChange(_, direction, _) = event {
+            match direction {
                 Direction::Up => {
                     self.set_current(if self.current == 0 {
                         self.list.len() - 1
@@ -57,8 +77,7 @@ impl<T> EventHandler for ListPanel<T> {
                 Direction::Down => {
                     self.set_current(self.current + 1);
                 }
-            },
-            _ => {}
+            }
         }
         false
     }
@@ -73,19 +92,18 @@ impl<T> Control for ListPanel<T> {
         self.dirty = true;
     }
 
-    fn paint(&mut self, canvas: &mut MonochromeCanvas, row: usize, col: usize) {
+    fn paint(&mut self, canvas: &mut Viewport) {
         if self.dirty {
             // Clear background
-            canvas.fill_rows(row, row + self.control_size.0, Pixel::Off);
+            canvas.fill(Pixel::Off);
 
             let start = self.offset;
-            let end = std::cmp::min(self.control_size.0 + self.offset, self.list.len());
+            let end = core::cmp::min(self.control_size.0 + self.offset, self.list.len());
 
             for (idx, (_, text)) in self.list[start..end].iter().enumerate() {
-                let idx = idx;
-                canvas.print(text.as_str(), idx + row, col + 1, Pixel::On);
+                canvas.print(text.as_str(), idx, 1, self.font, Pixel::On);
                 if (idx + self.offset) == self.current {
-                    canvas.invert_row(idx + row);
+                    canvas.invert_row(idx);
                 }
             }
 
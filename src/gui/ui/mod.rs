@@ -3,8 +3,10 @@ mod list_panel;
 mod tab_panel;
 mod text_panel;
 
+use crate::diagnostics::trace_span;
 use crate::events::{Event, EventHandler};
 use crate::gui::display::MonochromeCanvas;
+use crate::gui::viewport::Viewport;
 pub use list_panel::ListPanel;
 pub use tab_panel::TabPanel;
 pub use text_panel::TextPanel;
@@ -23,11 +25,10 @@ pub trait Control: EventHandler {
     ///
     /// # Arguments
     ///
-    /// * `canvas` - The current drawing canvas
-    /// * `row` - Row offset to start drawing
-    /// * `col` - Column offset to start drawing
+    /// * `canvas` - Viewport covering the area of the control, drawing is
+    ///   relative to the top left of the control and clipped to its size
     ///
-    fn paint(&mut self, canvas: &mut MonochromeCanvas, row: usize, col: usize);
+    fn paint(&mut self, canvas: &mut Viewport);
 }
 
 pub struct Surface<T: Control> {
@@ -45,8 +46,16 @@ impl<T: Control> Surface<T> {
 
     /// Paint the service
     pub fn paint(&mut self, canvas: &mut MonochromeCanvas) {
-        if self.child.is_some() {
-            self.child.as_mut().unwrap().paint(canvas, 0, 0);
+        let _span = trace_span!("paint").entered();
+        if let Some(child) = self.child.as_mut() {
+            child.paint(&mut Viewport::new(canvas, 0, 0, self.size.0, self.size.1));
+        }
+    }
+
+    /// Force the child to repaint, eg after something else has drawn on the canvas
+    pub fn set_repaint(&mut self) {
+        if let Some(child) = self.child.as_mut() {
+            child.set_repaint();
         }
     }
 
@@ -58,9 +67,11 @@ impl<T: Control> Surface<T> {
             .unwrap()
             .set_size(self.size.0, self.size.1)
     }
+}
 
-    /// Handle events
-    pub fn handle(&mut self, event: &Event) -> bool {
-        return self.child.as_mut().map_or(false, |c| c.handle(&event));
+impl<T: Control> EventHandler for Surface<T> {
+    /// Pass events on to the child control
+    fn handle(&mut self, event: &Event) -> bool {
+        self.child.as_mut().is_some_and(|c| c.handle(event))
     }
 }
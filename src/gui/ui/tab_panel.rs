@@ -1,6 +1,11 @@
 use crate::events::{Button, Event};
-use crate::gui::display::{Canvas, MonochromeCanvas, Pixel};
+use crate::gui::display::{Canvas, Pixel};
+use crate::gui::font::{Font, NX5};
+use crate::gui::text::Align;
 use crate::gui::ui::{Control, EventHandler};
+use crate::gui::viewport::Viewport;
+use alloc::boxed::Box;
+use alloc::string::String;
 
 ///
 /// Individual tab definition
@@ -11,30 +16,20 @@ pub struct Tab {
 }
 
 impl Tab {
-    fn paint(&self, canvas: &mut MonochromeCanvas, row: usize, col: usize, current: bool) {
+    /// Width of a tab including its edges
+    const WIDTH: usize = 42;
+
+    fn paint(&self, canvas: &mut Viewport, font: &dyn Font, current: bool) {
         // Render tab bar
-        canvas.set_pixel(col, 1, Pixel::On);
-        canvas.set_pixel(col, 2, Pixel::On);
-        canvas.set_pixel(col, 3, Pixel::On);
-        canvas.set_pixel(col, 4, Pixel::On);
-        canvas.set_pixel(col, 5, Pixel::On);
-        canvas.set_pixel(col, 6, Pixel::On);
-        canvas.set_pixel(col, 7, Pixel::On);
-        canvas.set_pixel(col + 41, 1, Pixel::On);
-        canvas.set_pixel(col + 41, 2, Pixel::On);
-        canvas.set_pixel(col + 41, 3, Pixel::On);
-        canvas.set_pixel(col + 41, 4, Pixel::On);
-        canvas.set_pixel(col + 41, 5, Pixel::On);
-        canvas.set_pixel(col + 41, 6, Pixel::On);
-        canvas.set_pixel(col + 41, 7, Pixel::On);
-
-        canvas.print(self.name.as_str(), row, col + 2, Pixel::On);
+        canvas.draw_vline(0, 1, 7, Pixel::On);
+        canvas.draw_vline(41, 1, 7, Pixel::On);
+
+        // Centre the name between the edges of the tab
+        canvas.print_aligned(&self.name, 0, 2, 38, font, Align::Centre, Pixel::On);
         if current {
-            canvas.invert_row_slice(row, col + 1, col + 41);
+            canvas.invert_row_slice(0, 1, 41);
         } else {
-            for idx in 1..41 {
-                canvas.set_pixel(col + idx, 0, Pixel::On);
-            }
+            canvas.draw_hline(1, 0, 40, Pixel::On);
         }
     }
 }
@@ -47,6 +42,7 @@ pub struct TabPanel {
     tabs: [Option<Tab>; 3],
     current: usize,
     size: (usize, usize),
+    font: &'static dyn Font,
 }
 
 impl TabPanel {
@@ -56,9 +52,18 @@ impl TabPanel {
             tabs: [None, None, None],
             current: 0,
             size: (0, 0),
+            font: &NX5,
         }
     }
 
+    ///
+    /// Set the font used for tab names
+    ///
+    pub fn set_font(&mut self, font: &'static dyn Font) {
+        self.font = font;
+        self.dirty = true;
+    }
+
     pub fn add_tab(&mut self, idx: usize, name: &str, control: Box<dyn Control>) {
         self.tabs[idx] = Some(Tab {
             name: String::from(name),
@@ -71,44 +76,33 @@ impl TabPanel {
         self.current = idx;
         self.dirty = true;
 
-        let tab = &mut self.tabs[idx];
-        if tab.is_some() {
-            let tab: &mut Tab = &mut tab.as_mut().unwrap();
+        if let Some(tab) = self.tabs[idx].as_mut() {
             tab.control.set_repaint();
         }
     }
 }
 
+impl Default for TabPanel {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl EventHandler for TabPanel {
     fn handle(&mut self, event: &Event) -> bool {
         let mut handled = false;
 
-        match event {
-            Event::ButtonChange(button, pressed, _shift) => match button {
-                Button::F1 => {
-                    if *pressed {
-                        self._set_tab(0);
-                    }
-                }
-                Button::F2 => {
-                    if *pressed {
-                        self._set_tab(1);
-                    }
-                }
-                Button::F3 => {
-                    if *pressed {
-                        self._set_tab(2);
-                    }
-                }
+        if let Event::ButtonChange(button, true, _shift) = event {
+            match button {
+                Button::F1 => self._set_tab(0),
+                Button::F2 => self._set_tab(1),
+                Button::F3 => self._set_tab(2),
                 _ => {}
-            },
-            _ => {}
+            }
         }
 
         // Pass on event
-        let tab = &mut self.tabs[self.current];
-        if tab.is_some() {
-            let tab: &mut Tab = &mut tab.as_mut().unwrap();
+        if let Some(tab) = self.tabs[self.current].as_mut() {
             handled = tab.control.handle(event);
         }
 
@@ -119,11 +113,8 @@ impl EventHandler for TabPanel {
 impl Control for TabPanel {
     fn set_size(&mut self, height: usize, width: usize) {
         self.size = (height, width);
-        for (_, tab) in self.tabs.iter_mut().enumerate() {
-            if tab.is_some() {
-                let tab: &mut Tab = &mut tab.as_mut().unwrap();
-                tab.control.set_size(height - 1, width);
-            }
+        for tab in self.tabs.iter_mut().flatten() {
+            tab.control.set_size(height - 1, width);
         }
     }
 
@@ -132,17 +123,17 @@ impl Control for TabPanel {
         self.dirty = true;
     }
 
-    fn paint(&mut self, canvas: &mut MonochromeCanvas, row: usize, col: usize) {
+    fn paint(&mut self, canvas: &mut Viewport) {
         // Render tab bar if required
         if self.dirty {
             // Clear the row
-            canvas.fill_row(row, Pixel::Off);
+            canvas.fill_row(0, Pixel::Off);
 
             // Paint each tab
-            for (idx, tab) in self.tabs.iter_mut().enumerate() {
-                if tab.is_some() {
-                    let tab: &mut Tab = &mut tab.as_mut().unwrap();
-                    tab.paint(canvas, row, col + (idx * 42) + idx, idx == self.current);
+            for (idx, tab) in self.tabs.iter().enumerate() {
+                if let Some(tab) = tab {
+                    let mut viewport = canvas.viewport(0, idx * (Tab::WIDTH + 1), 1, Tab::WIDTH);
+                    tab.paint(&mut viewport, self.font, idx == self.current);
                 }
             }
 
@@ -151,8 +142,10 @@ impl Control for TabPanel {
         }
 
         // Render the current control
-        self.tabs[self.current]
-            .as_mut()
-            .map(|t| t.control.paint(canvas, row + 1, col));
+        if let Some(tab) = self.tabs[self.current].as_mut() {
+            let (height, width) = self.size;
+            let mut viewport = canvas.viewport(1, 0, height - 1, width);
+            tab.control.paint(&mut viewport);
+        }
     }
 }
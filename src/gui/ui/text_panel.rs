@@ -1,16 +1,32 @@
-use crate::gui::display::{MonochromeCanvas, Canvas, Pixel};
+use crate::gui::display::{Canvas, Pixel};
+use crate::gui::font::{Font, NX5};
+use crate::gui::graphics::Graphics;
+use crate::gui::text::{measure_styled, parse_markup, wrap, Style};
 use crate::gui::ui::{Control, EventHandler};
+use crate::gui::viewport::Viewport;
 use crate::events::{Event, Direction};
+use alloc::string::String;
+use alloc::vec::Vec;
 
 ///
 /// Simple scrollable text panel
 ///
+/// Long lines are word wrapped to the width of the panel by default. With
+/// wrapping disabled lines are clipped instead and the encoder scrolls the text
+/// horizontally while shift is held.
+///
+/// Text set with `set_markup` can use inline styles, see `text::parse_markup`.
+///
 pub struct TextPanel {
     dirty: bool,
     text: String,
+    source: Vec<Vec<(char, Style)>>,
+    lines: Vec<Vec<(char, Style)>>,
+    wrap: bool,
     scroll_pos: (usize, usize),
     text_size: (usize, usize),
     control_size: (usize, usize),
+    font: &'static dyn Font,
 }
 
 impl TextPanel {
@@ -18,9 +34,13 @@ impl TextPanel {
         TextPanel {
             dirty: false,
             text: String::from(""),
+            source: Vec::new(),
+            lines: Vec::new(),
+            wrap: true,
             scroll_pos: (0, 0),
             text_size: (0, 0),
             control_size: (0, 0),
+            font: &NX5,
         }
     }
 
@@ -29,14 +49,37 @@ impl TextPanel {
     ///
     pub fn set_text(&mut self, text: &str) {
         self.text = String::from(text);
+        self.source = text
+            .split('\n')
+            .map(|line| line.chars().map(|c| (c, Style::default())).collect())
+            .collect();
+        self.layout();
+    }
 
-        // Calculate size
-        self.text_size = (
-            self.text.chars().filter(|&c| c == '\n').count() + 1,
-            self.text.lines().map(|x| x.len()).max().unwrap(),
-        );
+    ///
+    /// Set the current text including inline style markup
+    ///
+    pub fn set_markup(&mut self, text: &str) {
+        self.text = String::from(text);
+        self.source = text.split('\n').map(parse_markup).collect();
+        self.layout();
+    }
 
-        self.dirty = true;
+    ///
+    /// Word wrap lines that are wider than the panel, otherwise lines are clipped
+    /// and can be scrolled horizontally
+    ///
+    pub fn set_wrap(&mut self, wrap: bool) {
+        self.wrap = wrap;
+        self.layout();
+    }
+
+    ///
+    /// Set the font used for the text
+    ///
+    pub fn set_font(&mut self, font: &'static dyn Font) {
+        self.font = font;
+        self.layout();
     }
 
     ///
@@ -45,32 +88,72 @@ impl TextPanel {
     pub fn text(&self) -> &str {
         self.text.as_str()
     }
+
+    /// Break the text into the lines to display and calculate the size
+    fn layout(&mut self) {
+        let width = self.control_size.1;
+        self.lines = if self.wrap && width > 0 {
+            self.source
+                .iter()
+                .flat_map(|line| wrap(line, self.font, width))
+                .map(|line| line.to_vec())
+                .collect()
+        } else {
+            self.source.clone()
+        };
+
+        // Height in lines, width as the number of characters that can be scrolled
+        let scroll_width = self
+            .lines
+            .iter()
+            .map(|line| {
+                (0..line.len())
+                    .find(|&skip| measure_styled(&line[skip..], self.font) <= width)
+                    .unwrap_or(line.len())
+            })
+            .max()
+            .unwrap_or(0);
+        self.text_size = (self.lines.len(), if self.wrap { 0 } else { scroll_width });
+
+        let max_v_scroll = self.text_size.0.saturating_sub(self.control_size.0);
+        self.scroll_pos = (
+            core::cmp::min(self.scroll_pos.0, max_v_scroll),
+            core::cmp::min(self.scroll_pos.1, self.text_size.1),
+        );
+        self.dirty = true;
+    }
+}
+
+impl Default for TextPanel {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl Control for TextPanel {
     fn set_size(&mut self, height: usize, width: usize) {
         self.control_size = (height, width);
-        self.dirty = true;
+        self.layout();
     }
 
     fn set_repaint(&mut self) {
         self.dirty = true;
     }
 
-    fn paint(&mut self, canvas: &mut MonochromeCanvas, row: usize, col: usize) {
+    fn paint(&mut self, canvas: &mut Viewport) {
         if self.dirty {
-            let height = std::cmp::min(self.control_size.0, self.text_size.0);
-            let v_scroll = self.scroll_pos.0;
+            let height = core::cmp::min(self.control_size.0, self.text_size.0);
+            let (v_scroll, h_scroll) = self.scroll_pos;
 
             // Clear background
-            canvas.fill_rows(row, row + self.control_size.0, Pixel::Off);
+            canvas.fill(Pixel::Off);
 
-            for (idx, line) in self.text.lines().enumerate() {
+            for (idx, line) in self.lines.iter().enumerate() {
                 if idx < v_scroll { continue };
                 if idx >= height + v_scroll { break };
-                let current_row = row + idx - v_scroll;
-                canvas.fill_row(current_row, Pixel::Off);
-                canvas.print(line, current_row, col, Pixel::On);
+                let current_row = idx - v_scroll;
+                let line = &line[core::cmp::min(h_scroll, line.len())..];
+                paint_line(canvas, line, current_row, self.control_size.1, self.font);
             }
 
             self.dirty = false;
@@ -81,6 +164,25 @@ impl Control for TextPanel {
 impl EventHandler for TextPanel {
     fn handle(&mut self, event: &Event) -> bool {
         match event {
+            Event::EncoderChange(_encoder, direction, true) if !self.wrap => {
+                let h_scroll = self.scroll_pos.1;
+                match direction {
+                    Direction::Up => {
+                        if h_scroll < self.text_size.1 {
+                            self.scroll_pos.1 += 1;
+                            self.dirty = true;
+                        }
+                    },
+                    Direction::Down => {
+                        if h_scroll > 0 {
+                            self.scroll_pos.1 -= 1;
+                            self.dirty = true;
+                        }
+                    }
+                }
+
+                true
+            },
             Event::EncoderChange(_encoder, direction, _shift) => {
                 let v_scroll = self.scroll_pos.0;
                 let text_height = self.text_size.0;
@@ -108,4 +210,39 @@ impl EventHandler for TextPanel {
             _ => { false }
         }
     }
-}
\ No newline at end of file
+}
+
+///
+/// Paint a line of styled text into a row, characters that do not completely fit
+/// within `width` pixels are not painted
+///
+fn paint_line(
+    canvas: &mut Viewport,
+    line: &[(char, Style)],
+    row: usize,
+    width: usize,
+    font: &dyn Font,
+) {
+    let end = width;
+    let y = (row * 8 + 7usize.saturating_sub(font.height())) as i32;
+    let mut x = 0;
+    for &(c, style) in line {
+        let advance = style.advance(c, font);
+        if x + advance.saturating_sub(1) > end {
+            break;
+        }
+        let cell_end = core::cmp::min(x + advance, end);
+
+        canvas.print_char(c, row, x, font, Pixel::On);
+        if style.bold {
+            canvas.draw_char(x as i32 + 1, y, c, font, Pixel::On, None);
+        }
+        if style.underline {
+            canvas.draw_hline(x as i32, (row * 8 + 7) as i32, (cell_end - x) as i32, Pixel::On);
+        }
+        if style.inverse {
+            canvas.invert_row_slice(row, x, cell_end);
+        }
+        x += advance;
+    }
+}
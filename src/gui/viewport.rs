@@ -0,0 +1,198 @@
+///
+/// # Viewports
+///
+/// Sub-canvas covering a rectangle of a `MonochromeCanvas`, used to isolate
+/// controls from each other.
+///
+use super::bitmap::{Bitmap, BlitMode};
+use super::display::{Canvas, MonochromeCanvas, Pixel, Rect};
+use super::font::Font;
+use crate::events::Direction;
+
+///
+/// Part of a canvas that is drawn on as if it were a canvas of its own
+///
+/// Coordinates are relative to the top left of the viewport and drawing is
+/// clipped to the viewport (and any viewport it was created from). The
+/// viewport is positioned in rows so text and row operations line up with the
+/// row bands of the canvas.
+///
+/// Creating a viewport pushes a clip rectangle onto the canvas that is popped
+/// again when the viewport is dropped.
+///
+pub struct Viewport<'a> {
+    canvas: &'a mut MonochromeCanvas,
+    row: usize,
+    col: usize,
+    rows: usize,
+    width: usize,
+}
+
+impl<'a> Viewport<'a> {
+    ///
+    /// Create a viewport `rows` high and `width` pixels wide with its top left
+    /// corner at `row`, `col` of the canvas
+    ///
+    pub fn new(
+        canvas: &'a mut MonochromeCanvas,
+        row: usize,
+        col: usize,
+        rows: usize,
+        width: usize,
+    ) -> Self {
+        canvas.push_clip(Rect::new(col, row * 8, width, rows * 8));
+        Viewport {
+            canvas,
+            row,
+            col,
+            rows,
+            width,
+        }
+    }
+
+    ///
+    /// Viewport covering part of this viewport, positioned relative to it
+    ///
+    pub fn viewport(&mut self, row: usize, col: usize, rows: usize, width: usize) -> Viewport<'_> {
+        Viewport::new(self.canvas, self.row + row, self.col + col, rows, width)
+    }
+
+    ///
+    /// Height of the viewport in rows
+    ///
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    /// Convert viewport coordinates to canvas coordinates
+    fn translate(&self, x: i32, y: i32) -> (i32, i32) {
+        (x + self.col as i32, y + (self.row * 8) as i32)
+    }
+}
+
+impl Drop for Viewport<'_> {
+    fn drop(&mut self) {
+        self.canvas.pop_clip();
+    }
+}
+
+impl Canvas<Pixel> for Viewport<'_> {
+    fn width(&self) -> usize {
+        self.width
+    }
+
+    fn height(&self) -> usize {
+        self.rows * 8
+    }
+
+    fn is_dirty(&self) -> bool {
+        self.canvas.is_dirty()
+    }
+
+    fn clear_dirty_flag(&mut self) {
+        self.canvas.clear_dirty_flag();
+    }
+
+    fn invert(&mut self) {
+        for row in 0..self.rows {
+            self.invert_row(row);
+        }
+    }
+
+    fn invert_row(&mut self, row: usize) {
+        self.invert_row_slice(row, 0, self.width);
+    }
+
+    fn invert_row_slice(&mut self, row: usize, start_col: usize, end_col: usize) {
+        self.canvas
+            .invert_row_slice(self.row + row, self.col + start_col, self.col + end_col);
+    }
+
+    fn fill(&mut self, colour: Pixel) {
+        self.fill_rows(0, self.rows, colour);
+    }
+
+    fn fill_row(&mut self, row: usize, colour: Pixel) {
+        self.fill_rows(row, row + 1, colour);
+    }
+
+    fn fill_rows(&mut self, start_row: usize, end_row: usize, colour: Pixel) {
+        self.canvas
+            .fill_rows(self.row + start_row, self.row + end_row, colour);
+    }
+
+    fn set_pixel(&mut self, x: usize, y: usize, colour: Pixel) {
+        self.canvas
+            .set_pixel(self.col + x, self.row * 8 + y, colour);
+    }
+
+    fn pixel(&self, x: usize, y: usize) -> Option<Pixel> {
+        if x >= self.width || y >= self.height() {
+            return None;
+        }
+        self.canvas.pixel(self.col + x, self.row * 8 + y)
+    }
+
+    fn draw_hline(&mut self, x: i32, y: i32, w: i32, colour: Pixel) {
+        let (x, y) = self.translate(x, y);
+        self.canvas.draw_hline(x, y, w, colour);
+    }
+
+    fn draw_vline(&mut self, x: i32, y: i32, h: i32, colour: Pixel) {
+        let (x, y) = self.translate(x, y);
+        self.canvas.draw_vline(x, y, h, colour);
+    }
+
+    fn fill_rect(&mut self, x: i32, y: i32, w: i32, h: i32, colour: Pixel) {
+        let (x, y) = self.translate(x, y);
+        self.canvas.fill_rect(x, y, w, h, colour);
+    }
+
+    ///
+    /// Copy the top left of a canvas into the viewport
+    ///
+    fn copy_from(&mut self, canvas: &dyn Canvas<Pixel>) {
+        for y in 0..self.height() {
+            for x in 0..self.width {
+                if let Some(pixel) = canvas.pixel(x, y) {
+                    self.set_pixel(x, y, pixel);
+                }
+            }
+        }
+    }
+
+    fn blit(&mut self, bitmap: &Bitmap, x: i32, y: i32, mode: BlitMode) {
+        let (x, y) = self.translate(x, y);
+        self.canvas.blit(bitmap, x, y, mode);
+    }
+
+    fn print_clipped(
+        &mut self,
+        s: &str,
+        row: usize,
+        col: usize,
+        width: usize,
+        font: &dyn Font,
+        colour: Pixel,
+    ) {
+        self.canvas
+            .print_clipped(s, self.row + row, self.col + col, width, font, colour);
+    }
+
+    fn print_char(
+        &mut self,
+        c: char,
+        row: usize,
+        col: usize,
+        font: &dyn Font,
+        colour: Pixel,
+    ) -> usize {
+        self.canvas
+            .print_char(c, self.row + row, self.col + col, font, colour)
+    }
+
+    fn vscroll_rows(&mut self, row_start: usize, row_end: usize, direction: Direction) {
+        self.canvas
+            .vscroll_rows(self.row + row_start, self.row + row_end, direction);
+    }
+}
@@ -0,0 +1,57 @@
+//!
+//! # Usermode API library for a NI Maschine Mikro MK2
+//!
+//! Provides access to the buttons, pads, encoder, LEDs and display of the
+//! controller along with a simple UI toolkit and application framework.
+//!
+//! ## Features
+//!
+//! The crate is `no_std` (requires `alloc`) unless the `std` feature is enabled.
+//!
+//! * `std` (default) - Standard library support, eg loading palettes from files,
+//!   the timer `Scheduler` and diagnostics using the `tracing` crate
+//! * `hid` (default) - USB access to the controller via hidapi, requires `std` and `gui`
+//! * `gui` (default) - Display canvas, fonts and UI controls
+//! * `app` (default) - Application framework, requires `std` and `gui`
+//! * `rand` (default) - `Colour::random`, requires `std`
+//! * `png` (default) - Loading PNG images, requires `std` and `gui`
+//! * `simulator` - Software controller that needs no hardware, requires `gui`
+//!
+//! Connecting to the controller (requires the `hid` feature):
+//!
+//! ```no_run
+//! # #[cfg(feature = "hid")]
+//! # fn main() -> Result<(), mm::Error> {
+//! use mm::devices::{get_device, HidApi};
+//!
+//! let hid_api = HidApi::new()?;
+//! let ctlr = get_device(&hid_api)?;
+//! # Ok(())
+//! # }
+//! # #[cfg(not(feature = "hid"))]
+//! # fn main() {}
+//! ```
+//!
+#![no_std]
+
+extern crate alloc;
+#[cfg(feature = "std")]
+extern crate std;
+
+#[cfg(feature = "app")]
+pub mod app;
+pub mod colour;
+pub mod controller;
+#[cfg(any(feature = "hid", feature = "simulator"))]
+pub mod devices;
+mod diagnostics;
+pub mod error;
+pub mod events;
+#[cfg(feature = "gui")]
+pub mod gui;
+#[cfg(feature = "std")]
+pub mod metrics;
+
+pub use colour::Colour;
+pub use controller::Controller;
+pub use error::Error;
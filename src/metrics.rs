@@ -0,0 +1,277 @@
+///
+/// # I/O metrics
+///
+/// Latency and throughput measurements of the controller I/O loop, recorded by the
+/// device driver and the application `Runtime`.
+///
+use core::fmt;
+use std::time::{Duration, Instant};
+
+/// Window over which the display frame rate is calculated
+const FPS_WINDOW: Duration = Duration::from_secs(1);
+
+///
+/// Summary of a series of durations
+///
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct Stat {
+    count: u64,
+    total: Duration,
+    min: Duration,
+    max: Duration,
+    last: Duration,
+}
+
+impl Stat {
+    /// Add a measurement
+    pub fn record(&mut self, duration: Duration) {
+        if self.count == 0 || duration < self.min {
+            self.min = duration;
+        }
+        if duration > self.max {
+            self.max = duration;
+        }
+        self.count += 1;
+        self.total += duration;
+        self.last = duration;
+    }
+
+    /// Number of measurements
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+
+    /// Most recent measurement
+    pub fn last(&self) -> Duration {
+        self.last
+    }
+
+    /// Shortest measurement
+    pub fn min(&self) -> Duration {
+        self.min
+    }
+
+    /// Longest measurement
+    pub fn max(&self) -> Duration {
+        self.max
+    }
+
+    /// Average of all measurements
+    pub fn mean(&self) -> Duration {
+        if self.count == 0 {
+            Duration::ZERO
+        } else {
+            self.total.div_f64(self.count as f64)
+        }
+    }
+}
+
+impl fmt::Display for Stat {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            fmt,
+            "{:.1}ms ({:.1})",
+            self.mean().as_secs_f64() * 1000.0,
+            self.max.as_secs_f64() * 1000.0
+        )
+    }
+}
+
+///
+/// Latency and throughput of the controller I/O loop
+///
+/// * `report_to_event` - From a HID report being read to its events being
+///   handed to the application
+/// * `event_to_led` - From events being handed to the application to the next
+///   LED update being written to the device, only measured when handling the
+///   events changed the LEDs
+/// * `hid_write` - Time taken by each HID write
+/// * `fps` - Display frames written to the device per second
+///
+#[derive(Clone, Debug)]
+pub struct Metrics {
+    pub report_to_event: Stat,
+    pub event_to_led: Stat,
+    pub hid_write: Stat,
+    frames: u64,
+    fps: f64,
+    fps_window: (Instant, u64),
+    pending_report: Option<Instant>,
+    pending_event: Option<Instant>,
+    leds_changed: bool,
+}
+
+impl Metrics {
+    pub fn new() -> Self {
+        Metrics {
+            report_to_event: Stat::default(),
+            event_to_led: Stat::default(),
+            hid_write: Stat::default(),
+            frames: 0,
+            fps: 0.0,
+            fps_window: (Instant::now(), 0),
+            pending_report: None,
+            pending_event: None,
+            leds_changed: false,
+        }
+    }
+
+    /// Total display frames written to the device
+    pub fn frames(&self) -> u64 {
+        self.frames
+    }
+
+    /// Display frames written per second, over the last complete second
+    pub fn fps(&self) -> f64 {
+        self.fps
+    }
+
+    ///
+    /// Clear all measurements
+    ///
+    pub fn reset(&mut self) {
+        *self = Metrics::new();
+    }
+
+    ///
+    /// A report was read from the device
+    ///
+    pub fn report_received(&mut self, at: Instant) {
+        // Measure from the oldest report not yet handed to the application
+        self.pending_report.get_or_insert(at);
+    }
+
+    ///
+    /// Events are about to be handed to the application
+    ///
+    pub fn events_dispatching(&mut self) {
+        self.leds_changed = false;
+    }
+
+    ///
+    /// The LED state of the device was changed
+    ///
+    pub fn leds_changed(&mut self) {
+        self.leds_changed = true;
+    }
+
+    ///
+    /// Events from the device have been handed to the application
+    ///
+    pub fn events_dispatched(&mut self, at: Instant) {
+        if let Some(received) = self.pending_report.take() {
+            self.report_to_event
+                .record(at.saturating_duration_since(received));
+        }
+        // Only LED changes made while handling the events are measured
+        if self.leds_changed {
+            self.pending_event.get_or_insert(at);
+        }
+    }
+
+    ///
+    /// LED state was written to the device
+    ///
+    pub fn leds_sent(&mut self, at: Instant) {
+        if let Some(dispatched) = self.pending_event.take() {
+            self.event_to_led
+                .record(at.saturating_duration_since(dispatched));
+        }
+        self.leds_changed = false;
+    }
+
+    ///
+    /// A display frame was written to the device
+    ///
+    pub fn frame_sent(&mut self, at: Instant) {
+        self.frames += 1;
+
+        let (start, frames) = self.fps_window;
+        let elapsed = at.saturating_duration_since(start);
+        if elapsed >= FPS_WINDOW {
+            self.fps = frames as f64 / elapsed.as_secs_f64();
+            self.fps_window = (at, 1);
+        } else {
+            self.fps_window = (start, frames + 1);
+        }
+    }
+}
+
+impl Default for Metrics {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Display for Metrics {
+    /// Summary of metrics, one per line (mean and max in brackets)
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(fmt, "rpt>evt {}", self.report_to_event)?;
+        writeln!(fmt, "evt>led {}", self.event_to_led)?;
+        writeln!(fmt, "hid wr  {}", self.hid_write)?;
+        write!(fmt, "fps     {:.1}", self.fps)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stat_summary() {
+        let mut stat = Stat::default();
+        stat.record(Duration::from_millis(4));
+        stat.record(Duration::from_millis(2));
+        stat.record(Duration::from_millis(6));
+
+        assert_eq!(stat.count(), 3);
+        assert_eq!(stat.min(), Duration::from_millis(2));
+        assert_eq!(stat.max(), Duration::from_millis(6));
+        assert_eq!(stat.last(), Duration::from_millis(6));
+        assert_eq!(stat.mean(), Duration::from_millis(4));
+    }
+
+    #[test]
+    fn report_to_event_from_oldest_report() {
+        let start = Instant::now();
+        let at = |ms| start + Duration::from_millis(ms);
+        let mut metrics = Metrics::new();
+
+        metrics.report_received(at(0));
+        metrics.report_received(at(1));
+        metrics.events_dispatching();
+        metrics.events_dispatched(at(3));
+        assert_eq!(metrics.report_to_event.count(), 1);
+        assert_eq!(metrics.report_to_event.last(), Duration::from_millis(3));
+
+        // Nothing is pending
+        metrics.events_dispatching();
+        metrics.events_dispatched(at(4));
+        assert_eq!(metrics.report_to_event.count(), 1);
+    }
+
+    #[test]
+    fn event_to_led_only_when_dispatch_changed_leds() {
+        let start = Instant::now();
+        let at = |ms| start + Duration::from_millis(ms);
+        let mut metrics = Metrics::new();
+
+        // LEDs changed outside of dispatch (eg while rendering)
+        metrics.leds_changed();
+        metrics.events_dispatching();
+        metrics.events_dispatched(at(0));
+        metrics.leds_sent(at(5));
+        assert_eq!(metrics.event_to_led.count(), 0);
+
+        metrics.events_dispatching();
+        metrics.leds_changed();
+        metrics.events_dispatched(at(10));
+        metrics.leds_sent(at(12));
+        assert_eq!(metrics.event_to_led.count(), 1);
+        assert_eq!(metrics.event_to_led.last(), Duration::from_millis(2));
+
+        // Later LED updates are not attributed to the dispatch
+        metrics.leds_sent(at(100));
+        assert_eq!(metrics.event_to_led.count(), 1);
+    }
+}
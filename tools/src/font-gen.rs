@@ -1,11 +1,20 @@
 ///
 /// Simple tool to parse a 160x24 pixel grid into a font glyph lookup table
 ///
-/// All Glyphs are formatted for easy insertion into a data table
+/// All Glyphs are formatted for easy insertion into a data table, each entry is the
+/// width of the glyph followed by `width` bytes for each band of 8 rows
+///
+/// The grid starts at the `--first` code point (ASCII space by default), `--range`
+/// limits the output to part of the grid for a sparse range of code points. Cells
+/// without any black or white columns are output with a width of 0 and are treated
+/// as missing from the font, eg:
+///
+///   font-gen symbols.bmp --height 8 --first 2190 --range 2190-2193 --name ARROWS
 ///
 use clap::{AppSettings, Clap};
 use std::fs::File;
 use std::io::Read;
+use std::process;
 
 pub mod bitmap;
 
@@ -33,26 +42,28 @@ impl Glyph {
         width: usize,
         height: usize,
     ) -> Self {
-        let mut data: Vec<u8> = Vec::new();
+        // Columns that are part of the glyph start with a black or white pixel
+        let columns: Vec<usize> = (x_offset..(x_offset + width))
+            .filter(|&x| matches!(bitmap.pixel(x, y_offset), BLACK | WHITE))
+            .collect();
 
-        for x in x_offset..(x_offset + width) {
-            match bitmap.pixel(x, y_offset) {
-                BLACK | WHITE => {
-                    data.push(
-                        (0..height)
-                            .map(|y| match bitmap.pixel(x, y_offset + y) {
-                                BLACK => (1 << y) as u8,
-                                _ => 0u8,
-                            })
-                            .sum(),
-                    );
-                }
-                _ => {}
+        let mut data: Vec<u8> = Vec::new();
+        for band in 0..height.div_ceil(8) {
+            for &x in &columns {
+                data.push(
+                    (0..8)
+                        .filter(|bit| band * 8 + bit < height)
+                        .map(|bit| match bitmap.pixel(x, y_offset + band * 8 + bit) {
+                            BLACK => 1u8 << bit,
+                            _ => 0u8,
+                        })
+                        .sum(),
+                );
             }
         }
 
         Glyph {
-            width: data.len(),
+            width: columns.len(),
             data,
         }
     }
@@ -74,6 +85,28 @@ fn generate_glyphs(bm: bitmap::Bitmap, glyph_width: usize, glyph_height: usize)
     glyphs
 }
 
+///
+/// Parse a hex code point (eg `A0` or `U+00A0`)
+///
+fn parse_code_point(value: &str) -> Result<u32, String> {
+    let digits = value.trim_start_matches("U+").trim_start_matches("0x");
+    u32::from_str_radix(digits, 16).map_err(|_| format!("Invalid code point: {}", value))
+}
+
+///
+/// Parse a range of code points (eg `A0-DF`), a single code point is a range of one
+///
+fn parse_range(value: &str) -> Result<(u32, u32), String> {
+    let (start, end) = match value.split_once('-') {
+        Some((start, end)) => (parse_code_point(start)?, parse_code_point(end)?),
+        None => (parse_code_point(value)?, parse_code_point(value)?),
+    };
+    if end < start {
+        return Err(format!("Invalid range: {}", value));
+    }
+    Ok((start, end))
+}
+
 #[derive(Clap)]
 #[clap(version = "1.0", author = "Tim Savage <tim@savage.company>")]
 #[clap(setting = AppSettings::ColoredHelp)]
@@ -83,6 +116,15 @@ struct Opts {
     width: usize,
     #[clap(short, long, default_value = "5")]
     height: usize,
+    /// Name of the generated table
+    #[clap(short, long, default_value = "FONT")]
+    name: String,
+    /// Code point (hex) of the first glyph in the grid
+    #[clap(short, long, default_value = "20")]
+    first: String,
+    /// Range of code points (hex, eg A0-DF) to output, defaults to the whole grid
+    #[clap(short, long)]
+    range: Option<String>,
 }
 
 fn main() -> Result<(), bitmap::Error> {
@@ -98,29 +140,53 @@ fn main() -> Result<(), bitmap::Error> {
         opts.height,
     );
 
+    if glyphs.is_empty() {
+        exit("Bitmap is smaller than a glyph");
+    }
+    let first = parse_code_point(&opts.first).unwrap_or_else(|err| exit(&err));
+    let (start, end) = match &opts.range {
+        Some(range) => parse_range(range).unwrap_or_else(|err| exit(&err)),
+        None => (first, first + glyphs.len() as u32 - 1),
+    };
+    if start < first {
+        exit("Range starts before the first glyph");
+    }
+
+    // Code points past the end of the grid are missing from the font
+    let missing = Glyph {
+        width: 0,
+        data: Vec::new(),
+    };
+
     println!(
-        "pub const FONT: [(u8, [u8; {}]); {}] = [",
-        opts.width,
-        glyphs.len()
+        "pub const {}: [(u8, &[u8]); {}] = [",
+        opts.name,
+        end - start + 1
     );
-    for idx in 0..glyphs.len() {
-        let mut slices = glyphs[idx].data.clone();
-        for _ in 0..(opts.width - glyphs[idx].width) {
-            slices.push(0);
-        }
-
+    for code_point in start..=end {
+        let glyph = glyphs
+            .get((code_point - first) as usize)
+            .unwrap_or(&missing);
         println!(
-            "    ({}, [{}]),  // {}",
-            glyphs[idx].width,
-            slices
+            "    ({}, &[{}]),  // {}",
+            glyph.width,
+            glyph
+                .data
                 .iter()
                 .map(|c| format!("{}", c))
                 .collect::<Vec<String>>()
                 .join(", "),
-            ((0x20 + idx) as u8) as char
+            char::from_u32(code_point)
+                .filter(|c| !c.is_control())
+                .unwrap_or(' ')
         );
     }
     println!("];");
 
     Ok(())
 }
+
+fn exit(message: &str) -> ! {
+    eprintln!("{}", message);
+    process::exit(1)
+}